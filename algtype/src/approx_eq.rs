@@ -0,0 +1,361 @@
+//! 近似相等
+//!
+//! 浮点数有误差，不宜直接比较。[`ApproxEq`] 在给定的容差 [`Tolerance`] 内比较，
+//! 并给出首个不同之处 [`Diff`]。可使用 [`GenericApproxEq`] 自动实现，
+//! 此时要求变体相同，且各字段近似相等。
+//!
+//! [`assert_approx_eq!`](crate::assert_approx_eq) 在不相等时报告不同之处。
+
+use crate::{visit_tuple, Generic, One, Product, Repr, Sum, Zero};
+use core::fmt;
+
+/// 浮点数的容差
+///
+/// 两数相等、差的绝对值不超过 `epsilon`、或相距不超过 `ulps` 个可表示的数时，视为近似相等。
+/// NaN 与任何数都不近似相等。
+///
+/// ```
+/// # use algtype::approx_eq::{ApproxEq, Tolerance};
+/// let tol = Tolerance::new(0.0, 1);
+/// assert!(1.0f64.approx_eq(&(1.0 + f64::EPSILON), &tol));
+/// assert!(!1.0f64.approx_eq(&(1.0 + 2.0 * f64::EPSILON), &tol));
+/// assert!(1.0f64.approx_eq(&1.1, &Tolerance::new(0.2, 0)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    /// 允许的绝对误差
+    pub epsilon: f64,
+    /// 允许相距的可表示的数的个数
+    pub ulps: u64,
+}
+
+impl Tolerance {
+    /// 创建容差
+    pub const fn new(epsilon: f64, ulps: u64) -> Self {
+        Self { epsilon, ulps }
+    }
+}
+
+impl Default for Tolerance {
+    /// 绝对误差 `1e-6`，或相距 4 个数
+    fn default() -> Self {
+        Self::new(1e-6, 4)
+    }
+}
+
+/// 不同之处
+///
+/// [`path`](Diff::path) 为从外到内的字段位置，序列的元素以下标为位置。
+/// 路径指向变体不同之处，或值不同的叶子。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Diff {
+    len: usize,
+    path: [usize; Diff::MAX_DEPTH],
+}
+
+impl Diff {
+    /// 路径的最大长度，更深处会被截去
+    pub const MAX_DEPTH: usize = 16;
+
+    /// 此处不同
+    pub const fn here() -> Self {
+        Self {
+            len: 0,
+            path: [0; Self::MAX_DEPTH],
+        }
+    }
+
+    /// 在外层的位置 `index` 处
+    pub fn within(mut self, index: usize) -> Self {
+        self.path.copy_within(..Self::MAX_DEPTH - 1, 1);
+        self.path[0] = index;
+        self.len = (self.len + 1).min(Self::MAX_DEPTH);
+        self
+    }
+
+    /// 从外到内的字段位置
+    pub fn path(&self) -> &[usize] {
+        &self.path[..self.len]
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("differ at ")?;
+        if self.len == 0 {
+            return f.write_str("root");
+        }
+        for (i, x) in self.path().iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 可以近似比较者
+///
+/// 建议使用 [`GenericApproxEq`] 自动实现。
+pub trait ApproxEq {
+    /// 若不近似相等，返回首个不同之处
+    fn approx_diff(&self, other: &Self, tol: &Tolerance) -> Option<Diff>;
+
+    /// 是否近似相等
+    fn approx_eq(&self, other: &Self, tol: &Tolerance) -> bool {
+        self.approx_diff(other, tol).is_none()
+    }
+}
+
+/// 断言近似相等
+///
+/// 容差默认为 [`Tolerance::default`]。不相等时 panic，并报告首个不同之处。
+///
+/// ```should_panic
+/// # use algtype::assert_approx_eq;
+/// assert_approx_eq!((1.0, 2.0), (1.0, 2.0 + 1e-9));
+/// // panic: assertion `left ≈ right` failed: differ at 1
+/// assert_approx_eq!((1.0, 2.0), (1.0, 2.1));
+/// ```
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_approx_eq!($left, $right, $crate::approx_eq::Tolerance::default())
+    };
+    ($left:expr, $right:expr, $tol:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if let Some(diff) = $crate::approx_eq::ApproxEq::approx_diff(left, right, &$tol) {
+                    panic!(
+                        "assertion `left ≈ right` failed: {}\n  left: {:?}\n right: {:?}",
+                        diff, left, right,
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// 表示上的 [`ApproxEq`]
+///
+/// `index` 是字段的位置。
+pub trait GApproxEq: Repr {
+    fn g_approx_diff(
+        this: &Self::Ref<'_>,
+        other: &Self::Ref<'_>,
+        tol: &Tolerance,
+        index: usize,
+    ) -> Option<Diff>;
+}
+
+impl GApproxEq for Zero {
+    fn g_approx_diff(
+        this: &Self::Ref<'_>,
+        _other: &Self::Ref<'_>,
+        _tol: &Tolerance,
+        _index: usize,
+    ) -> Option<Diff> {
+        match *this {}
+    }
+}
+
+impl GApproxEq for One {
+    fn g_approx_diff(
+        _this: &Self::Ref<'_>,
+        _other: &Self::Ref<'_>,
+        _tol: &Tolerance,
+        _index: usize,
+    ) -> Option<Diff> {
+        None
+    }
+}
+
+impl<T: GApproxEq, R: GApproxEq> GApproxEq for Sum<T, R> {
+    fn g_approx_diff(
+        this: &Self::Ref<'_>,
+        other: &Self::Ref<'_>,
+        tol: &Tolerance,
+        _index: usize,
+    ) -> Option<Diff> {
+        match (this, other) {
+            (Sum::This(x), Sum::This(y)) => T::g_approx_diff(x, y, tol, 0),
+            (Sum::Next(x), Sum::Next(y)) => R::g_approx_diff(x, y, tol, 0),
+            _ => Some(Diff::here()),
+        }
+    }
+}
+
+impl<T: ApproxEq, R: GApproxEq> GApproxEq for Product<T, R> {
+    fn g_approx_diff(
+        this: &Self::Ref<'_>,
+        other: &Self::Ref<'_>,
+        tol: &Tolerance,
+        index: usize,
+    ) -> Option<Diff> {
+        match this.0.approx_diff(other.0, tol) {
+            Some(d) => Some(d.within(index)),
+            None => R::g_approx_diff(&this.1, &other.1, tol, index + 1),
+        }
+    }
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericApproxEq`]，将会自动实现 [`ApproxEq`]。
+///
+/// ```
+/// # use algtype::{Generic, approx_eq::{ApproxEq, GenericApproxEq, Tolerance}};
+/// #[derive(Generic)]
+/// struct Body {
+///     mass: f32,
+///     pos: (f64, f64),
+/// }
+///
+/// impl GenericApproxEq for Body {}
+///
+/// let a = Body { mass: 1.0, pos: (0.1 + 0.2, 0.0) };
+/// let b = Body { mass: 1.0, pos: (0.3, 1.0) };
+/// assert_eq!(a.approx_diff(&b, &Tolerance::default()).unwrap().path(), [1, 1]);
+/// ```
+pub trait GenericApproxEq: Generic
+where
+    Self::Repr: GApproxEq,
+{
+}
+
+impl<T: GenericApproxEq> ApproxEq for T
+where
+    T::Repr: GApproxEq,
+{
+    fn approx_diff(&self, other: &Self, tol: &Tolerance) -> Option<Diff> {
+        T::Repr::g_approx_diff(&self.as_repr(), &other.as_repr(), tol, 0)
+    }
+}
+
+// ADT
+
+impl<T: ApproxEq> GenericApproxEq for Option<T> {}
+
+impl<T: ApproxEq, E: ApproxEq> GenericApproxEq for Result<T, E> {}
+
+impl GenericApproxEq for bool {}
+
+// 数组
+
+impl<T, const N: usize> GenericApproxEq for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GApproxEq,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    ($($tys:ident)*) => {
+        impl <$($tys: ApproxEq),*> GenericApproxEq for ($($tys,)*) {}
+    };
+}
+
+visit_tuple!(impl_tuple);
+
+// 浮点数
+
+macro_rules! impl_float {
+    ($($ty:ty => $int:ty)*) => {$(
+        impl ApproxEq for $ty {
+            fn approx_diff(&self, other: &Self, tol: &Tolerance) -> Option<Diff> {
+                let (a, b) = (*self, *other);
+                if a == b || (a as f64 - b as f64).abs() <= tol.epsilon {
+                    return None;
+                }
+                if a.is_nan() || b.is_nan() {
+                    return Some(Diff::here());
+                }
+                // 使位的顺序与数的顺序一致
+                let key = |x: $ty| {
+                    let x = x.to_bits() as $int;
+                    if x < 0 { <$int>::MIN - x } else { x }
+                };
+                match key(a).abs_diff(key(b)) as u64 <= tol.ulps {
+                    true => None,
+                    false => Some(Diff::here()),
+                }
+            }
+        }
+    )*};
+}
+
+impl_float!(f32 => i32 f64 => i64);
+
+// 精确比较者
+
+macro_rules! impl_exact {
+    ($($ty:ty)*) => {$(
+        impl ApproxEq for $ty {
+            #[inline]
+            fn approx_diff(&self, other: &Self, _tol: &Tolerance) -> Option<Diff> {
+                (self != other).then(Diff::here)
+            }
+        }
+    )*};
+}
+
+impl_exact!(
+    u8 u16 u32 u64 u128 usize
+    i8 i16 i32 i64 i128 isize
+    char str &str
+);
+
+// 序列
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_diff(&self, other: &Self, tol: &Tolerance) -> Option<Diff> {
+        if self.len() != other.len() {
+            return Some(Diff::here());
+        }
+        self.iter()
+            .zip(other)
+            .enumerate()
+            .find_map(|(i, (x, y))| Some(x.approx_diff(y, tol)?.within(i)))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for &[T] {
+    fn approx_diff(&self, other: &Self, tol: &Tolerance) -> Option<Diff> {
+        (**self).approx_diff(other, tol)
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::{ApproxEq, Diff, GenericApproxEq, Tolerance};
+    use alloc::{boxed::Box, string::String, vec::Vec};
+
+    impl<T: ApproxEq> GenericApproxEq for Box<T> {}
+
+    impl ApproxEq for Box<str> {
+        fn approx_diff(&self, other: &Self, tol: &Tolerance) -> Option<Diff> {
+            self[..].approx_diff(&other[..], tol)
+        }
+    }
+
+    impl<T: ApproxEq> ApproxEq for Box<[T]> {
+        fn approx_diff(&self, other: &Self, tol: &Tolerance) -> Option<Diff> {
+            self[..].approx_diff(&other[..], tol)
+        }
+    }
+
+    impl ApproxEq for String {
+        fn approx_diff(&self, other: &Self, tol: &Tolerance) -> Option<Diff> {
+            self[..].approx_diff(&other[..], tol)
+        }
+    }
+
+    impl<T: ApproxEq> ApproxEq for Vec<T> {
+        fn approx_diff(&self, other: &Self, tol: &Tolerance) -> Option<Diff> {
+            self[..].approx_diff(&other[..], tol)
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! 类型状态的构建器
+//!
+//! 对于只有一个变体的类型，[`Builder`] 记录每个字段是否已设置，且记录在类型中，
+//! 因此只有所有字段都已设置时，[`build`](Builder::build) 才能通过编译。
+//! 实现 `Default` 的字段可用 [`optional`](Builder::optional) 标记为可选，构建时若仍未设置则取默认值。
+//!
+//! 字段按位置设置，位置可用 [`index`](crate::index) 中的 `I0`、`I1` 等。
+//! 名字只在运行时可知（[`Generic::META`]），无法用于类型层面的检查，故不提供按名字的设置。
+//!
+//! ```
+//! # use algtype::{Generic, builder::Builder, index::*};
+//! #[derive(Generic, Debug, PartialEq)]
+//! struct Server {
+//!     host: String,
+//!     port: u16,
+//!     verbose: bool,
+//! }
+//!
+//! let s: Server = Builder::new()
+//!     .optional::<I2>()
+//!     .set::<I1>(8080)
+//!     .set::<I0>("localhost".into())
+//!     .build();
+//! assert_eq!(s, Server { host: "localhost".into(), port: 8080, verbose: false });
+//! ```
+//!
+//! 即使字段实现了 `Default`，未设置也未标记为可选时仍无法构建：
+//!
+//! ```compile_fail
+//! # use algtype::{Generic, builder::Builder, index::*};
+//! #[derive(Generic)]
+//! struct Request {
+//!     method: &'static str,
+//!     path: String,
+//! }
+//!
+//! // 缺少第 0 个字段
+//! let _: Request = Builder::new().set::<I1>("/".into()).build();
+//! ```
+
+use crate::{
+    flatten::Single,
+    index::{Here, There},
+    Generic, One, Product, Repr,
+};
+use core::marker::PhantomData;
+
+/// 未设置的字段
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Unset;
+
+/// 已设置的字段
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Set<X>(pub X);
+
+/// 标记为可选的字段，构建时取默认值
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Optional;
+
+/// 字段组成的积类型
+pub trait Fields: Repr {
+    /// 各字段都未设置的状态
+    type Empty;
+    /// 各字段都未设置
+    fn empty() -> Self::Empty;
+}
+
+impl Fields for One {
+    type Empty = One;
+
+    fn empty() -> Self::Empty {
+        One
+    }
+}
+
+impl<X, R: Fields> Fields for Product<X, R> {
+    type Empty = Product<Unset, R::Empty>;
+
+    fn empty() -> Self::Empty {
+        Product(Unset, R::empty())
+    }
+}
+
+/// 位置 `I` 处的字段
+pub trait At<I>: Repr {
+    /// 字段的类型
+    type Type;
+}
+
+impl<X, R: Repr> At<Here> for Product<X, R> {
+    type Type = X;
+}
+
+impl<X, R: At<I>, I> At<There<I>> for Product<X, R> {
+    type Type = R::Type;
+}
+
+/// 把状态中位置 `I` 处的字段的状态换为 `V`，如 [`Set`] 或 [`Optional`]
+pub trait Fill<I, V> {
+    /// 设置后的状态
+    type Output;
+    /// 设置
+    fn fill(self, v: V) -> Self::Output;
+}
+
+impl<S, R, V> Fill<Here, V> for Product<S, R> {
+    type Output = Product<V, R>;
+
+    fn fill(self, v: V) -> Self::Output {
+        Product(v, self.1)
+    }
+}
+
+impl<S, R: Fill<I, V>, I, V> Fill<There<I>, V> for Product<S, R> {
+    type Output = Product<S, R::Output>;
+
+    fn fill(self, v: V) -> Self::Output {
+        Product(self.0, self.1.fill(v))
+    }
+}
+
+/// 各字段都已设置或标记为可选的状态，可转换成字段组成的积类型 `P`
+pub trait Complete<P> {
+    /// 转换，可选的字段取默认值
+    fn complete(self) -> P;
+}
+
+impl Complete<One> for One {
+    fn complete(self) -> One {
+        One
+    }
+}
+
+impl<X, R: Complete<P>, P> Complete<Product<X, P>> for Product<Set<X>, R> {
+    fn complete(self) -> Product<X, P> {
+        Product(self.0 .0, self.1.complete())
+    }
+}
+
+impl<X: Default, R: Complete<P>, P> Complete<Product<X, P>> for Product<Optional, R> {
+    fn complete(self) -> Product<X, P> {
+        Product(X::default(), self.1.complete())
+    }
+}
+
+type FieldsOf<T> = <<T as Generic>::Repr as Single>::Product;
+
+/// 构建器
+///
+/// `S` 为状态，记录各字段是否已设置及其值。
+pub struct Builder<T: Generic, S = <FieldsOf<T> as Fields>::Empty>
+where
+    T::Repr: Single,
+{
+    state: S,
+    _t: PhantomData<fn() -> T>,
+}
+
+impl<T: Generic> Builder<T>
+where
+    T::Repr: Single,
+    FieldsOf<T>: Fields,
+{
+    /// 创建各字段都未设置的构建器
+    pub fn new() -> Self {
+        Builder {
+            state: FieldsOf::<T>::empty(),
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T: Generic> Default for Builder<T>
+where
+    T::Repr: Single,
+    FieldsOf<T>: Fields,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Generic, S> Builder<T, S>
+where
+    T::Repr: Single,
+{
+    /// 设置位置 `I` 处的字段，可以覆盖已设置的
+    pub fn set<I>(self, x: <FieldsOf<T> as At<I>>::Type) -> Builder<T, S::Output>
+    where
+        FieldsOf<T>: At<I>,
+        S: Fill<I, Set<<FieldsOf<T> as At<I>>::Type>>,
+    {
+        Builder {
+            state: self.state.fill(Set(x)),
+            _t: PhantomData,
+        }
+    }
+
+    /// 设置位置 `I` 处的字段为默认值，之后仍可以覆盖
+    pub fn set_default<I>(self) -> Builder<T, S::Output>
+    where
+        FieldsOf<T>: At<I>,
+        <FieldsOf<T> as At<I>>::Type: Default,
+        S: Fill<I, Set<<FieldsOf<T> as At<I>>::Type>>,
+    {
+        self.set(Default::default())
+    }
+
+    /// 标记位置 `I` 处的字段为可选，构建时若仍未设置则取默认值
+    ///
+    /// 与 [`set_default`](Self::set_default) 不同，默认值在构建时才创建。
+    pub fn optional<I>(self) -> Builder<T, S::Output>
+    where
+        FieldsOf<T>: At<I>,
+        <FieldsOf<T> as At<I>>::Type: Default,
+        S: Fill<I, Optional>,
+    {
+        Builder {
+            state: self.state.fill(Optional),
+            _t: PhantomData,
+        }
+    }
+
+    /// 构建，要求各字段都已设置或标记为可选
+    pub fn build(self) -> T
+    where
+        S: Complete<FieldsOf<T>>,
+    {
+        T::from_repr(<T::Repr as Single>::wrap(self.state.complete()))
+    }
+}
@@ -0,0 +1,179 @@
+//! 按载荷的类型构造和拆解变体
+//!
+//! 类似 frunk 的 `Coproduct`。若某变体只有一个类型为 `P` 的字段，称 `P` 为其载荷。
+//! [`Inject`] 能按载荷的类型选取变体，进行构造和拆解，变体的位置（见 [`index`](crate::index)）由类型推导得出。
+//! 若没有或有多个载荷为 `P` 的变体，则会编译错误。
+//!
+//! ```
+//! # use algtype::{Generic, coproduct::Inject};
+//! # use std::num::ParseIntError;
+//! #[derive(Generic, Debug)]
+//! enum Error {
+//!     Parse(ParseIntError),
+//!     Range(u64),
+//!     Unknown,
+//! }
+//!
+//! let e = Error::inject("x".parse::<u8>().unwrap_err());
+//! assert!(matches!(e, Error::Parse(_)));
+//! assert_eq!(Error::Range(1).extract::<u64, _>().unwrap(), 1);
+//! assert!(Error::Unknown.extract::<u64, _>().is_err());
+//! ```
+//!
+//! ```compile_fail
+//! # use algtype::coproduct::Inject;
+//! // 两个变体的载荷都是 `u8`，无法确定
+//! let _ = Result::<u8, u8>::inject(1);
+//! ```
+
+use crate::{
+    index::{Here, There},
+    Generic, One, Product, Repr, Sum,
+};
+
+/// 在和类型中选取载荷为 `P` 的变体，`I` 为其位置
+pub trait Injector<P, I>: Repr + Sized {
+    /// 构造此变体
+    fn inject(p: P) -> Self;
+    /// 若是此变体，则取出载荷
+    fn extract(this: Self) -> Result<P, Self>;
+    /// 若是此变体，则取出载荷的引用
+    fn extract_ref<'a>(this: Self::Ref<'a>) -> Option<&'a P>
+    where
+        Self: 'a;
+}
+
+impl<P, R: Repr> Injector<P, Here> for Sum<Product<P, One>, R> {
+    fn inject(p: P) -> Self {
+        Sum::This(Product(p, One))
+    }
+
+    fn extract(this: Self) -> Result<P, Self> {
+        match this {
+            Sum::This(Product(p, One)) => Ok(p),
+            x => Err(x),
+        }
+    }
+
+    fn extract_ref<'a>(this: Self::Ref<'a>) -> Option<&'a P>
+    where
+        Self: 'a,
+    {
+        match this {
+            Sum::This(Product(p, One)) => Some(p),
+            Sum::Next(_) => None,
+        }
+    }
+}
+
+impl<T: Repr, R: Injector<P, I>, P, I> Injector<P, There<I>> for Sum<T, R> {
+    fn inject(p: P) -> Self {
+        Sum::Next(R::inject(p))
+    }
+
+    fn extract(this: Self) -> Result<P, Self> {
+        match this {
+            Sum::This(x) => Err(Sum::This(x)),
+            Sum::Next(x) => R::extract(x).map_err(Sum::Next),
+        }
+    }
+
+    fn extract_ref<'a>(this: Self::Ref<'a>) -> Option<&'a P>
+    where
+        Self: 'a,
+    {
+        match this {
+            Sum::This(_) => None,
+            Sum::Next(x) => R::extract_ref(x),
+        }
+    }
+}
+
+/// 有载荷为 `P` 的变体，`I` 为其位置
+///
+/// 为有此变体的类型自动实现，可用作约束。
+pub trait HasVariant<P, I>: Generic + Sized {
+    /// 构造此变体
+    fn from_payload(p: P) -> Self;
+    /// 若是此变体，则取出载荷
+    fn into_payload(self) -> Result<P, Self>;
+    /// 若是此变体，则取出载荷的引用
+    fn payload(&self) -> Option<&P>;
+}
+
+impl<S: Generic, P, I> HasVariant<P, I> for S
+where
+    S::Repr: Injector<P, I>,
+{
+    fn from_payload(p: P) -> Self {
+        S::from_repr(S::Repr::inject(p))
+    }
+
+    fn into_payload(self) -> Result<P, Self> {
+        S::Repr::extract(self.into_repr()).map_err(S::from_repr)
+    }
+
+    fn payload(&self) -> Option<&P> {
+        S::Repr::extract_ref(self.as_repr())
+    }
+}
+
+/// 按载荷的类型构造和拆解变体
+///
+/// 为所有实现 [`Generic`] 的类型自动实现。
+pub trait Inject: Generic + Sized {
+    /// 构造载荷为 `P` 的变体
+    fn inject<P, I>(p: P) -> Self
+    where
+        Self: HasVariant<P, I>,
+    {
+        Self::from_payload(p)
+    }
+
+    /// 若是载荷为 `P` 的变体，则取出载荷，否则原样返回
+    fn extract<P, I>(self) -> Result<P, Self>
+    where
+        Self: HasVariant<P, I>,
+    {
+        self.into_payload()
+    }
+
+    /// 若是载荷为 `P` 的变体，则取出载荷的引用
+    fn extract_ref<P, I>(&self) -> Option<&P>
+    where
+        Self: HasVariant<P, I>,
+    {
+        self.payload()
+    }
+}
+
+impl<S: Generic> Inject for S {}
+
+/// 通过 [`Inject::inject`] 实现 `From`
+///
+/// 孤儿规则不允许自动实现 `From`，此宏可为一个类型的多个载荷一并实现。
+///
+/// ```
+/// # use algtype::{Generic, inject_from};
+/// #[derive(Generic, Debug)]
+/// enum Event {
+///     Key(char),
+///     Resize((u16, u16)),
+/// }
+///
+/// inject_from!(Event: char, (u16, u16));
+///
+/// assert!(matches!(Event::from('a'), Event::Key('a')));
+/// let e: Event = (80, 24).into();
+/// assert!(matches!(e, Event::Resize((80, 24))));
+/// ```
+#[macro_export]
+macro_rules! inject_from {
+    ($ty:ty: $($payload:ty),* $(,)?) => {$(
+        impl ::core::convert::From<$payload> for $ty {
+            fn from(p: $payload) -> Self {
+                $crate::coproduct::Inject::inject(p)
+            }
+        }
+    )*};
+}
@@ -0,0 +1,464 @@
+//! 按字段位置读写分隔的文本，如 CSV 与 TSV
+//!
+//! 每行对应一个仅有一个变体的类型的值，各列按 [`Product`] 的顺序以 `FromStr` 解析、以 `Display` 格式化。
+//! 实现 [`Record`] 者可以读写，可使用 [`GenericRecord`] 自动实现。
+//!
+//! 含有分隔符、引号或换行的格子加上引号，其中的引号写两次；仅有一格且为空时也加引号。
+//! 读取时跳过空行，但少于两列时空行也是一条记录。错误带有出错处的行号和列号（均从 1 开始）。
+//! 表头是字段的名字（[`Generic::META`]），没有名字者为序号。
+
+use crate::{visit_tuple, Generic, One, Product, Repr, Sum, VariantMeta, Zero};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{self, Display},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+/// 错误的种类
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// 引号未闭合，或闭合后不是分隔符
+    Quote,
+    /// 列数不符
+    Length { expected: usize, found: usize },
+    /// 表头与字段的名字不符
+    Header { expected: String, found: String },
+    /// 格子无法解析
+    Parse {
+        field: Option<&'static str>,
+        message: String,
+    },
+}
+
+/// 读取的错误，带有出错处的位置
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    line: usize,
+    column: Option<usize>,
+    kind: ErrorKind,
+}
+
+impl Error {
+    fn new(line: usize, column: Option<usize>, kind: ErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+
+    /// 错误的种类
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// 记录起始的行号
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// 列号，与整行有关的错误没有列号
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+/// 打印为如 `line 3, column 2 (size): invalid digit found in string`
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}", self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ", column {column}")?;
+        }
+        match &self.kind {
+            ErrorKind::Quote => f.write_str(": malformed quoted cell"),
+            ErrorKind::Length { expected, found } => {
+                write!(f, ": expected {expected} columns, found {found}")
+            }
+            ErrorKind::Header { expected, found } => {
+                write!(f, ": expected header `{expected}`, found `{found}`")
+            }
+            ErrorKind::Parse {
+                field: Some(field),
+                message,
+            } => write!(f, " ({field}): {message}"),
+            ErrorKind::Parse {
+                field: None,
+                message,
+            } => write!(f, ": {message}"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// 可按列读写者
+///
+/// 建议使用 [`GenericRecord`] 自动实现。
+pub trait Record: Sized {
+    /// 列数
+    const LEN: usize;
+    /// 第 `index` 列的名字
+    fn column_name(index: usize) -> Option<&'static str>;
+    /// 格式化各列
+    fn to_cells(&self, out: &mut Vec<String>);
+    /// 从各列解析，`cells` 的长度为 [`Record::LEN`]
+    ///
+    /// 出错时返回列的序号（从 0 开始）和错误。
+    fn from_cells(cells: &[String]) -> Result<Self, (usize, ErrorKind)>;
+}
+
+/// 表示上的 [`Record`]，用于仅有一个变体的和类型
+pub trait GRecord: Repr + Sized {
+    const LEN: usize;
+    fn g_to_cells(this: &Self::Ref<'_>, out: &mut Vec<String>);
+    fn g_from_cells(
+        variant: Option<&VariantMeta>,
+        cells: &[String],
+    ) -> Result<Self, (usize, ErrorKind)>;
+}
+
+/// 表示上的 [`Record`]，用于积类型
+///
+/// `variant` 是所在变体的名字信息，`index` 是字段的位置。
+pub trait GRecordFields: Repr + Sized {
+    const LEN: usize;
+    fn g_to_cells(this: &Self::Ref<'_>, out: &mut Vec<String>);
+    fn g_from_cells(
+        variant: Option<&VariantMeta>,
+        index: usize,
+        cells: &[String],
+    ) -> Result<Self, (usize, ErrorKind)>;
+}
+
+impl<T: GRecordFields> GRecord for Sum<T, Zero> {
+    const LEN: usize = T::LEN;
+
+    fn g_to_cells(this: &Self::Ref<'_>, out: &mut Vec<String>) {
+        match this {
+            Sum::This(x) => T::g_to_cells(x, out),
+            Sum::Next(x) => match *x {},
+        }
+    }
+
+    fn g_from_cells(
+        variant: Option<&VariantMeta>,
+        cells: &[String],
+    ) -> Result<Self, (usize, ErrorKind)> {
+        T::g_from_cells(variant, 0, cells).map(Sum::This)
+    }
+}
+
+impl GRecordFields for One {
+    const LEN: usize = 0;
+
+    fn g_to_cells(_this: &Self::Ref<'_>, _out: &mut Vec<String>) {}
+
+    fn g_from_cells(
+        _variant: Option<&VariantMeta>,
+        _index: usize,
+        _cells: &[String],
+    ) -> Result<Self, (usize, ErrorKind)> {
+        Ok(One)
+    }
+}
+
+impl<T: FromStr + Display, R: GRecordFields> GRecordFields for Product<T, R>
+where
+    T::Err: Display,
+{
+    const LEN: usize = 1 + R::LEN;
+
+    fn g_to_cells(this: &Self::Ref<'_>, out: &mut Vec<String>) {
+        out.push(this.0.to_string());
+        R::g_to_cells(&this.1, out);
+    }
+
+    fn g_from_cells(
+        variant: Option<&VariantMeta>,
+        index: usize,
+        cells: &[String],
+    ) -> Result<Self, (usize, ErrorKind)> {
+        let x = cells[index].parse().map_err(|e: T::Err| {
+            let kind = ErrorKind::Parse {
+                field: variant.and_then(|v| v.fields.name(index)),
+                message: e.to_string(),
+            };
+            (index, kind)
+        })?;
+        Ok(Product(x, R::g_from_cells(variant, index + 1, cells)?))
+    }
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericRecord`]，将会自动实现 [`Record`]。
+///
+/// ```
+/// # use algtype::{Generic, csv::{Dialect, GenericRecord}};
+/// #[derive(Generic, Debug, PartialEq)]
+/// struct Row {
+///     name: String,
+///     size: u32,
+/// }
+///
+/// impl GenericRecord for Row {}
+///
+/// let text = "name,size\n\"Smith, J\",3\nLee,x\n";
+/// let mut rows = Dialect::CSV.reader_with_header::<Row>(text).unwrap();
+/// let row = Row { name: "Smith, J".into(), size: 3 };
+/// assert_eq!(rows.next(), Some(Ok(row)));
+/// let e = rows.next().unwrap().unwrap_err();
+/// assert_eq!(e.to_string(), "line 3, column 2 (size): invalid digit found in string");
+///
+/// let mut out = String::new();
+/// Dialect::TSV.write_header::<Row>(&mut out);
+/// Dialect::TSV.write(&mut out, &Row { name: "a\tb".into(), size: 1 });
+/// assert_eq!(out, "name\tsize\n\"a\tb\"\t1\n");
+/// ```
+pub trait GenericRecord: Generic
+where
+    Self::Repr: GRecord,
+{
+}
+
+impl<T: GenericRecord> Record for T
+where
+    T::Repr: GRecord,
+{
+    const LEN: usize = T::Repr::LEN;
+
+    fn column_name(index: usize) -> Option<&'static str> {
+        T::META?.variants.first()?.fields.name(index)
+    }
+
+    fn to_cells(&self, out: &mut Vec<String>) {
+        T::Repr::g_to_cells(&self.as_repr(), out);
+    }
+
+    fn from_cells(cells: &[String]) -> Result<Self, (usize, ErrorKind)> {
+        let variant = T::META.and_then(|m| m.variants.first());
+        T::Repr::g_from_cells(variant, cells).map(T::from_repr)
+    }
+}
+
+/// 分隔符与引号
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dialect {
+    pub delimiter: char,
+    pub quote: char,
+}
+
+impl Dialect {
+    /// 逗号分隔
+    pub const CSV: Self = Self {
+        delimiter: ',',
+        quote: '"',
+    };
+    /// 制表符分隔
+    pub const TSV: Self = Self {
+        delimiter: '\t',
+        quote: '"',
+    };
+
+    /// 逐行读取，没有表头
+    pub fn reader<T: Record>(self, text: &str) -> Reader<'_, T> {
+        Reader {
+            dialect: self,
+            text,
+            line: 1,
+            _marker: PhantomData,
+        }
+    }
+
+    /// 逐行读取，首行为表头，须与 [`Record::column_name`] 相符
+    pub fn reader_with_header<T: Record>(self, text: &str) -> Result<Reader<'_, T>, Error> {
+        let mut reader = self.reader(text);
+        let (line, cells) = reader.cells().unwrap_or(Ok((1, Vec::new())))?;
+        check_len::<T>(line, &cells)?;
+        for (i, found) in cells.into_iter().enumerate() {
+            let expected = header::<T>(i);
+            if found != expected {
+                let kind = ErrorKind::Header { expected, found };
+                return Err(Error::new(line, Some(i + 1), kind));
+            }
+        }
+        Ok(reader)
+    }
+
+    /// 写入表头
+    pub fn write_header<T: Record>(self, out: &mut String) {
+        let cells: Vec<_> = (0..T::LEN).map(header::<T>).collect();
+        self.write_cells(out, &cells);
+    }
+
+    /// 写入一行
+    pub fn write<T: Record>(self, out: &mut String, x: &T) {
+        let mut cells = Vec::with_capacity(T::LEN);
+        x.to_cells(&mut cells);
+        self.write_cells(out, &cells);
+    }
+
+    fn write_cells(self, out: &mut String, cells: &[String]) {
+        for (i, cell) in cells.iter().enumerate() {
+            if i != 0 {
+                out.push(self.delimiter);
+            }
+            let special = |c| c == self.delimiter || c == self.quote || c == '\n' || c == '\r';
+            // 仅有的一格为空时，加上引号以免读作空行
+            let blank = cells.len() == 1 && cell.is_empty();
+            if !blank && !cell.contains(special) {
+                out.push_str(cell);
+                continue;
+            }
+            out.push(self.quote);
+            for c in cell.chars() {
+                if c == self.quote {
+                    out.push(c);
+                }
+                out.push(c);
+            }
+            out.push(self.quote);
+        }
+        out.push('\n');
+    }
+}
+
+fn header<T: Record>(index: usize) -> String {
+    T::column_name(index).map_or_else(|| index.to_string(), Into::into)
+}
+
+fn check_len<T: Record>(line: usize, cells: &[String]) -> Result<(), Error> {
+    match cells.len() == T::LEN {
+        true => Ok(()),
+        false => {
+            let kind = ErrorKind::Length {
+                expected: T::LEN,
+                found: cells.len(),
+            };
+            Err(Error::new(line, None, kind))
+        }
+    }
+}
+
+/// 逐行读取的迭代器，参见 [`Dialect::reader`]
+pub struct Reader<'a, T> {
+    dialect: Dialect,
+    text: &'a str,
+    line: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Record> Reader<'_, T> {
+    /// 读取下一条记录的各格，及其起始的行号
+    fn cells(&mut self) -> Option<Result<(usize, Vec<String>), Error>> {
+        // 跳过空行，但少于两列时空行也是一条记录
+        loop {
+            let rest = self.text.trim_start_matches('\r');
+            match rest.strip_prefix('\n') {
+                Some(rest) if T::LEN == 0 => {
+                    self.text = rest;
+                    self.line += 1;
+                    return Some(Ok((self.line - 1, Vec::new())));
+                }
+                Some(_) if T::LEN == 1 => break,
+                Some(rest) => {
+                    self.text = rest;
+                    self.line += 1;
+                }
+                None if rest.is_empty() => return None,
+                None => break,
+            }
+        }
+        let Dialect { delimiter, quote } = self.dialect;
+        let line = self.line;
+        let mut cells = Vec::new();
+        let mut chars = self.text.char_indices().peekable();
+        loop {
+            let mut cell = String::new();
+            let quoted = chars.next_if(|&(_, c)| c == quote).is_some();
+            if quoted {
+                loop {
+                    match chars.next() {
+                        Some((_, c)) if c == quote => match chars.next_if(|&(_, c)| c == quote) {
+                            Some(_) => cell.push(quote),
+                            None => break,
+                        },
+                        Some((_, c)) => {
+                            self.line += (c == '\n') as usize;
+                            cell.push(c);
+                        }
+                        None => {
+                            self.text = "";
+                            let column = Some(cells.len() + 1);
+                            return Some(Err(Error::new(line, column, ErrorKind::Quote)));
+                        }
+                    }
+                }
+            }
+            // 直到分隔符或行尾，加引号者其后不能有别的字符
+            let end = loop {
+                match chars.next() {
+                    Some((_, c)) if c == delimiter => break false,
+                    Some((_, '\n')) | None => break true,
+                    Some((_, '\r')) if matches!(chars.peek(), Some((_, '\n')) | None) => {}
+                    Some(_) if quoted => {
+                        // 跳过此行的其余部分
+                        let rest = chars.find(|&(_, c)| c == '\n');
+                        self.text = rest.map_or("", |(i, _)| &self.text[i + 1..]);
+                        self.line += 1;
+                        let column = Some(cells.len() + 1);
+                        return Some(Err(Error::new(line, column, ErrorKind::Quote)));
+                    }
+                    Some((_, c)) => cell.push(c),
+                }
+            };
+            cells.push(cell);
+            if end {
+                break;
+            }
+        }
+        let consumed = chars.peek().map_or(self.text.len(), |&(i, _)| i);
+        self.text = &self.text[consumed..];
+        self.line += 1;
+        Some(Ok((line, cells)))
+    }
+}
+
+impl<T: Record> Iterator for Reader<'_, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (line, cells) = match self.cells()? {
+            Ok(x) => x,
+            Err(e) => return Some(Err(e)),
+        };
+        if let Err(e) = check_len::<T>(line, &cells) {
+            return Some(Err(e));
+        }
+        Some(T::from_cells(&cells).map_err(|(i, kind)| Error::new(line, Some(i + 1), kind)))
+    }
+}
+
+// 数组
+
+impl<T, const N: usize> GenericRecord for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GRecord,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    ($($tys:ident)*) => {
+        impl <$($tys: FromStr + Display),*> GenericRecord for ($($tys,)*)
+        where
+            $($tys::Err: Display),*
+        {
+        }
+    };
+}
+
+visit_tuple!(impl_tuple);
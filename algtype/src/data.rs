@@ -0,0 +1,278 @@
+//! 递归遍历，类似 Haskell 的 SYB（scrap your boilerplate）
+//!
+//! 实现 [`Data`] 者能列出其直接的子项，据此可以递归地找到嵌套的某类型的所有值，
+//! 而无需为每个类型编写访问器。可使用 [`GenericData`] 自动实现，其子项为当前变体的各字段。
+//!
+//! ```
+//! # use algtype::{Generic, data::{Data, GenericData}};
+//! #[derive(Generic)]
+//! struct Config {
+//!     name: String,
+//!     servers: Vec<(String, u16)>,
+//! }
+//!
+//! impl GenericData for Config {}
+//!
+//! let mut cfg = Config {
+//!     name: " main ".into(),
+//!     servers: vec![(" a ".into(), 80), ("b".into(), 81)],
+//! };
+//! cfg.everywhere_mut(|s: &mut String| *s = s.trim().into());
+//! assert_eq!(cfg.name, "main");
+//! assert_eq!(cfg.servers[0].0, "a");
+//! assert_eq!(cfg.everything(0, |sum, port: &u16| sum + port), 161);
+//! ```
+
+use crate::{visit_tuple, Generic, One, Product, Repr, Sum, Zero};
+use core::any::Any;
+
+/// 转换为 `dyn Any`，为所有 `'static` 类型自动实现
+///
+/// 使 `dyn Data` 能够向下转型，而无需 trait upcasting（Rust 1.86）。
+pub trait AsAny: Any {
+    /// 转换引用
+    fn as_any(&self) -> &dyn Any;
+    /// 转换可变引用
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// 可以遍历子项者
+///
+/// 建议使用 [`GenericData`] 自动实现。
+pub trait Data: AsAny {
+    /// 对每个直接的子项调用 `f`
+    fn gmap(&self, f: &mut dyn FnMut(&dyn Data));
+    /// 对每个直接的子项的可变引用调用 `f`
+    fn gmap_mut(&mut self, f: &mut dyn FnMut(&mut dyn Data));
+
+    /// 对嵌套的（包括自身）每个 `T` 类型的值调用 `f`，先内层后外层
+    fn everywhere_mut<T: Any>(&mut self, mut f: impl FnMut(&mut T))
+    where
+        Self: Sized,
+    {
+        everywhere_mut(self, &mut f);
+    }
+
+    /// 用 `f` 折叠嵌套的（包括自身）每个 `T` 类型的值，先外层后内层
+    fn everything<T: Any, R>(&self, init: R, mut f: impl FnMut(R, &T) -> R) -> R
+    where
+        Self: Sized,
+    {
+        let mut acc = Some(init);
+        everything(self, &mut |x| acc = acc.take().map(|acc| f(acc, x)));
+        acc.unwrap()
+    }
+}
+
+fn everywhere_mut<T: Any>(x: &mut dyn Data, f: &mut dyn FnMut(&mut T)) {
+    x.gmap_mut(&mut |x| everywhere_mut(x, f));
+    if let Some(x) = x.as_any_mut().downcast_mut() {
+        f(x);
+    }
+}
+
+fn everything<T: Any>(x: &dyn Data, f: &mut dyn FnMut(&T)) {
+    if let Some(x) = x.as_any().downcast_ref() {
+        f(x);
+    }
+    x.gmap(&mut |x| everything(x, f));
+}
+
+/// 表示上的 [`Data`]
+pub trait GData: Repr {
+    fn g_gmap(this: &Self::Ref<'_>, f: &mut dyn FnMut(&dyn Data));
+    fn g_gmap_mut(this: Self::Mut<'_>, f: &mut dyn FnMut(&mut dyn Data));
+}
+
+impl GData for Zero {
+    fn g_gmap(this: &Self::Ref<'_>, _f: &mut dyn FnMut(&dyn Data)) {
+        match *this {}
+    }
+
+    fn g_gmap_mut(this: Self::Mut<'_>, _f: &mut dyn FnMut(&mut dyn Data)) {
+        match this {}
+    }
+}
+
+impl GData for One {
+    fn g_gmap(_this: &Self::Ref<'_>, _f: &mut dyn FnMut(&dyn Data)) {}
+
+    fn g_gmap_mut(_this: Self::Mut<'_>, _f: &mut dyn FnMut(&mut dyn Data)) {}
+}
+
+impl<T: GData, R: GData> GData for Sum<T, R> {
+    fn g_gmap(this: &Self::Ref<'_>, f: &mut dyn FnMut(&dyn Data)) {
+        match this {
+            Sum::This(x) => T::g_gmap(x, f),
+            Sum::Next(x) => R::g_gmap(x, f),
+        }
+    }
+
+    fn g_gmap_mut(this: Self::Mut<'_>, f: &mut dyn FnMut(&mut dyn Data)) {
+        match this {
+            Sum::This(x) => T::g_gmap_mut(x, f),
+            Sum::Next(x) => R::g_gmap_mut(x, f),
+        }
+    }
+}
+
+impl<T: Data, R: GData> GData for Product<T, R> {
+    fn g_gmap(this: &Self::Ref<'_>, f: &mut dyn FnMut(&dyn Data)) {
+        f(this.0);
+        R::g_gmap(&this.1, f);
+    }
+
+    fn g_gmap_mut(this: Self::Mut<'_>, f: &mut dyn FnMut(&mut dyn Data)) {
+        f(this.0);
+        R::g_gmap_mut(this.1, f);
+    }
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericData`]，将会自动实现 [`Data`]。
+///
+/// 递归的类型无法如此实现，因为约束会无限展开。此时可用 [`generic_gmap`] 和
+/// [`generic_gmap_mut`] 手动实现：
+///
+/// ```
+/// # use algtype::{Generic, data::{self, Data}};
+/// #[derive(Generic)]
+/// enum Tree {
+///     Leaf(i32),
+///     Node(Box<Tree>, Box<Tree>),
+/// }
+///
+/// impl Data for Tree {
+///     fn gmap(&self, f: &mut dyn FnMut(&dyn Data)) {
+///         data::generic_gmap(self, f)
+///     }
+///     fn gmap_mut(&mut self, f: &mut dyn FnMut(&mut dyn Data)) {
+///         data::generic_gmap_mut(self, f)
+///     }
+/// }
+///
+/// let leaf = |x| Box::new(Tree::Leaf(x));
+/// let tree = Tree::Node(leaf(1), Box::new(Tree::Node(leaf(2), leaf(3))));
+/// assert_eq!(tree.everything(0, |sum, x: &i32| sum + x), 6);
+/// ```
+pub trait GenericData: Generic + 'static
+where
+    Self::Repr: GData,
+{
+}
+
+impl<T: GenericData> Data for T
+where
+    T::Repr: GData,
+{
+    fn gmap(&self, f: &mut dyn FnMut(&dyn Data)) {
+        generic_gmap(self, f);
+    }
+
+    fn gmap_mut(&mut self, f: &mut dyn FnMut(&mut dyn Data)) {
+        generic_gmap_mut(self, f);
+    }
+}
+
+/// 基于 [`Generic`] 的 [`Data::gmap`]
+pub fn generic_gmap<T: Generic>(x: &T, f: &mut dyn FnMut(&dyn Data))
+where
+    T::Repr: GData,
+{
+    T::Repr::g_gmap(&x.as_repr(), f);
+}
+
+/// 基于 [`Generic`] 的 [`Data::gmap_mut`]
+pub fn generic_gmap_mut<T: Generic>(x: &mut T, f: &mut dyn FnMut(&mut dyn Data))
+where
+    T::Repr: GData,
+{
+    T::Repr::g_gmap_mut(x.as_mut_repr(), f);
+}
+
+// ADT
+
+impl<T: Data> GenericData for Option<T> {}
+
+impl<T: Data, E: Data> GenericData for Result<T, E> {}
+
+impl GenericData for bool {}
+
+// 数组
+
+impl<T: 'static, const N: usize> GenericData for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GData,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    ($($tys:ident)*) => {
+        impl <$($tys: Data),*> GenericData for ($($tys,)*) {}
+    };
+}
+
+visit_tuple!(impl_tuple);
+
+// 没有子项者
+
+macro_rules! impl_leaf {
+    ($($ty:ty)*) => {$(
+        impl Data for $ty {
+            #[inline]
+            fn gmap(&self, _f: &mut dyn FnMut(&dyn Data)) {}
+            #[inline]
+            fn gmap_mut(&mut self, _f: &mut dyn FnMut(&mut dyn Data)) {}
+        }
+    )*};
+}
+
+impl_leaf!(
+    u8 u16 u32 u64 u128 usize
+    i8 i16 i32 i64 i128 isize
+    f32 f64 char &'static str
+);
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::{Data, GenericData};
+    use alloc::{boxed::Box, string::String, vec::Vec};
+
+    impl_leaf!(String Box<str>);
+
+    impl<T: Data> GenericData for Box<T> {}
+
+    impl<T: Data> Data for Box<[T]> {
+        fn gmap(&self, f: &mut dyn FnMut(&dyn Data)) {
+            self.iter().for_each(|x| f(x));
+        }
+
+        fn gmap_mut(&mut self, f: &mut dyn FnMut(&mut dyn Data)) {
+            self.iter_mut().for_each(|x| f(x));
+        }
+    }
+
+    impl<T: Data> Data for Vec<T> {
+        fn gmap(&self, f: &mut dyn FnMut(&dyn Data)) {
+            self.iter().for_each(|x| f(x));
+        }
+
+        fn gmap_mut(&mut self, f: &mut dyn FnMut(&mut dyn Data)) {
+            self.iter_mut().for_each(|x| f(x));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! 以 trait 对象访问变体的载荷
+//!
+//! 若 enum 的每个变体都只有一个字段，且这些字段实现同一个 trait，
+//! [`Dispatch`] 能取出当前变体的载荷作为该 trait 的对象，而无需手写 `match`。
+//!
+//! 稳定的 Rust 不能对任意 trait 抽象“转换为 trait 对象”，因此需以 [`coerce_dyn!`](crate::coerce_dyn)
+//! 为 trait 对象实现 [`Coerce`]。`core::fmt` 中的 `Debug` 和 `Display` 已经实现。
+//!
+//! ```
+//! # use algtype::{Generic, coerce_dyn, dispatch::Dispatch};
+//! trait Plugin {
+//!     fn name(&self) -> &str;
+//!     fn run(&mut self, input: u32) -> u32;
+//! }
+//!
+//! struct Double;
+//! struct Count(u32);
+//!
+//! impl Plugin for Double {
+//!     fn name(&self) -> &str { "double" }
+//!     fn run(&mut self, input: u32) -> u32 { input * 2 }
+//! }
+//!
+//! impl Plugin for Count {
+//!     fn name(&self) -> &str { "count" }
+//!     fn run(&mut self, _: u32) -> u32 { self.0 += 1; self.0 }
+//! }
+//!
+//! coerce_dyn!(Plugin);
+//!
+//! #[derive(Generic)]
+//! enum Plugins {
+//!     Double(Double),
+//!     Count(Count),
+//! }
+//!
+//! let mut p = Plugins::Count(Count(0));
+//! assert_eq!(p.as_dyn::<dyn Plugin>().name(), "count");
+//! p.as_dyn_mut::<dyn Plugin>().run(7);
+//! assert_eq!(p.as_dyn_mut::<dyn Plugin>().run(7), 2);
+//! assert_eq!(Plugins::Double(Double).as_dyn::<dyn Plugin>().name(), "double");
+//! ```
+
+use crate::{Generic, One, Product, Repr, Sum, Zero};
+use core::fmt::{Debug, Display};
+
+/// `T` 的引用能转换为 `Self` 的引用
+///
+/// `Self` 通常是 trait 对象，以 [`coerce_dyn!`](crate::coerce_dyn) 实现。
+pub trait Coerce<T> {
+    /// 转换引用
+    fn coerce(x: &T) -> &Self;
+    /// 转换可变引用
+    fn coerce_mut(x: &mut T) -> &mut Self;
+}
+
+/// 为 trait 对象实现 [`Coerce`]
+///
+/// 对每个给出的 trait `Trait`，为 `dyn Trait` 实现 `Coerce<T>`，其中 `T: Trait`。
+/// 孤儿规则要求 `Trait` 定义在调用此宏的 crate 中。
+///
+/// ```
+/// # use algtype::{coerce_dyn, dispatch::Coerce};
+/// trait Area {
+///     fn area(&self) -> f64;
+/// }
+///
+/// struct Square(f64);
+///
+/// impl Area for Square {
+///     fn area(&self) -> f64 { self.0 * self.0 }
+/// }
+///
+/// coerce_dyn!(Area);
+///
+/// let a: &dyn Area = Coerce::coerce(&Square(2.0));
+/// assert_eq!(a.area(), 4.0);
+/// ```
+#[macro_export]
+macro_rules! coerce_dyn {
+    ($($tr:path),* $(,)?) => {$(
+        impl<'a, T: $tr + 'a> $crate::dispatch::Coerce<T> for dyn $tr + 'a {
+            fn coerce(x: &T) -> &Self {
+                x
+            }
+
+            fn coerce_mut(x: &mut T) -> &mut Self {
+                x
+            }
+        }
+    )*};
+}
+
+coerce_dyn!(Debug, Display);
+
+/// 取出当前变体唯一的字段，作为 `D` 的引用
+pub trait GDispatch<D: ?Sized>: Repr {
+    /// 取出引用
+    fn g_as_dyn<'a>(this: Self::Ref<'a>) -> &'a D
+    where
+        Self: 'a;
+    /// 取出可变引用
+    fn g_as_dyn_mut<'a>(this: Self::Mut<'a>) -> &'a mut D
+    where
+        Self: 'a;
+}
+
+impl<D: ?Sized> GDispatch<D> for Zero {
+    fn g_as_dyn<'a>(this: Zero) -> &'a D {
+        match this {}
+    }
+
+    fn g_as_dyn_mut<'a>(this: Zero) -> &'a mut D {
+        match this {}
+    }
+}
+
+impl<D: ?Sized + Coerce<T>, T, R: GDispatch<D>> GDispatch<D> for Sum<Product<T, One>, R> {
+    fn g_as_dyn<'a>(this: Self::Ref<'a>) -> &'a D
+    where
+        Self: 'a,
+    {
+        match this {
+            Sum::This(Product(x, One)) => D::coerce(x),
+            Sum::Next(r) => R::g_as_dyn(r),
+        }
+    }
+
+    fn g_as_dyn_mut<'a>(this: Self::Mut<'a>) -> &'a mut D
+    where
+        Self: 'a,
+    {
+        match this {
+            Sum::This(Product(x, One)) => D::coerce_mut(x),
+            Sum::Next(r) => R::g_as_dyn_mut(r),
+        }
+    }
+}
+
+/// 以 trait 对象访问当前变体的载荷
+///
+/// 为所有实现 [`Generic`] 的类型自动实现。
+/// 仅当每个变体都只有一个字段，且 `D` 对各字段实现 [`Coerce`] 时，方法可用。
+pub trait Dispatch: Generic {
+    /// 取出载荷，作为 `D` 的引用
+    fn as_dyn<D: ?Sized>(&self) -> &D
+    where
+        Self::Repr: GDispatch<D>,
+    {
+        Self::Repr::g_as_dyn(self.as_repr())
+    }
+
+    /// 取出载荷，作为 `D` 的可变引用
+    fn as_dyn_mut<D: ?Sized>(&mut self) -> &mut D
+    where
+        Self::Repr: GDispatch<D>,
+    {
+        Self::Repr::g_as_dyn_mut(self.as_mut_repr())
+    }
+}
+
+impl<T: Generic> Dispatch for T {}
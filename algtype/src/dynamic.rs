@@ -0,0 +1,438 @@
+//! 自描述的动态值
+//!
+//! [`DynValue`] 是数据的通用中间形式，可用于脚本、查看界面、测试数据之类。
+//! 实现 [`Dynamic`] 者可与之互转，可使用 [`GenericDynamic`] 自动实现。
+//!
+//! 若类型有名字信息（[`Generic::META`]），则变体和字段以名字为键，否则以序号为键。
+
+use crate::{visit_tuple, Generic, One, Product, Repr, Sum, VariantMeta, Zero};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+/// 动态值
+#[derive(Clone, Debug, PartialEq)]
+pub enum DynValue {
+    Unit,
+    Bool(bool),
+    Int(i128),
+    UInt(u128),
+    Float(f64),
+    Str(String),
+    Seq(Vec<DynValue>),
+    /// enum 的值，包含变体的键和字段
+    Variant {
+        key: Key,
+        fields: Vec<(Key, DynValue)>,
+    },
+    /// struct 的值，包含字段
+    Struct {
+        fields: Vec<(Key, DynValue)>,
+    },
+}
+
+/// 变体或字段的键
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    Name(Cow<'static, str>),
+    Index(usize),
+}
+
+impl Key {
+    fn new(name: Option<&'static str>, index: usize) -> Self {
+        match name {
+            Some(name) => Key::Name(Cow::Borrowed(name)),
+            None => Key::Index(index),
+        }
+    }
+
+    fn matches(&self, name: Option<&str>, index: usize) -> bool {
+        match self {
+            Key::Name(n) => Some(&**n) == name,
+            Key::Index(i) => *i == index,
+        }
+    }
+}
+
+/// 可与动态值互转者
+///
+/// 建议使用 [`GenericDynamic`] 自动实现。
+pub trait Dynamic: Sized {
+    /// 转换成动态值
+    fn to_dyn(&self) -> DynValue;
+    /// 从动态值转换
+    ///
+    /// 若动态值与类型不符，则返回 `None`。
+    fn from_dyn(v: DynValue) -> Option<Self>;
+}
+
+/// 表示上的 [`Dynamic`]，用于和类型
+///
+/// `variants` 是从当前变体开始的名字信息。
+pub trait GDynamic: Repr + Sized {
+    /// 变体的数量
+    const COUNT: usize;
+    /// 写入字段，返回变体的序号
+    fn g_to_dyn(
+        this: &Self::Ref<'_>,
+        variants: Option<&[VariantMeta]>,
+        fields: &mut Vec<(Key, DynValue)>,
+    ) -> usize;
+    /// 从第 `index` 个变体及其字段转换
+    fn g_from_dyn(
+        index: usize,
+        variants: Option<&[VariantMeta]>,
+        fields: &mut Vec<(Key, DynValue)>,
+    ) -> Option<Self>;
+}
+
+/// 表示上的 [`Dynamic`]，用于积类型
+///
+/// `variant` 是所在变体的名字信息，`index` 是字段的位置。
+pub trait GDynamicFields: Repr + Sized {
+    fn g_to_dyn(
+        this: &Self::Ref<'_>,
+        variant: Option<&VariantMeta>,
+        index: usize,
+        fields: &mut Vec<(Key, DynValue)>,
+    );
+    fn g_from_dyn(
+        variant: Option<&VariantMeta>,
+        index: usize,
+        fields: &mut Vec<(Key, DynValue)>,
+    ) -> Option<Self>;
+}
+
+impl GDynamic for Zero {
+    const COUNT: usize = 0;
+
+    fn g_to_dyn(
+        this: &Self::Ref<'_>,
+        _variants: Option<&[VariantMeta]>,
+        _fields: &mut Vec<(Key, DynValue)>,
+    ) -> usize {
+        match *this {}
+    }
+
+    fn g_from_dyn(
+        _index: usize,
+        _variants: Option<&[VariantMeta]>,
+        _fields: &mut Vec<(Key, DynValue)>,
+    ) -> Option<Self> {
+        None
+    }
+}
+
+impl<T: GDynamicFields, R: GDynamic> GDynamic for Sum<T, R> {
+    const COUNT: usize = 1 + R::COUNT;
+
+    fn g_to_dyn(
+        this: &Self::Ref<'_>,
+        variants: Option<&[VariantMeta]>,
+        fields: &mut Vec<(Key, DynValue)>,
+    ) -> usize {
+        match this {
+            Sum::This(x) => {
+                T::g_to_dyn(x, variants.and_then(<[_]>::first), 0, fields);
+                0
+            }
+            Sum::Next(x) => 1 + R::g_to_dyn(x, variants.and_then(|v| v.get(1..)), fields),
+        }
+    }
+
+    fn g_from_dyn(
+        index: usize,
+        variants: Option<&[VariantMeta]>,
+        fields: &mut Vec<(Key, DynValue)>,
+    ) -> Option<Self> {
+        match index {
+            0 => T::g_from_dyn(variants.and_then(<[_]>::first), 0, fields).map(Sum::This),
+            i => R::g_from_dyn(i - 1, variants.and_then(|v| v.get(1..)), fields).map(Sum::Next),
+        }
+    }
+}
+
+impl GDynamicFields for One {
+    fn g_to_dyn(
+        _this: &Self::Ref<'_>,
+        _variant: Option<&VariantMeta>,
+        _index: usize,
+        _fields: &mut Vec<(Key, DynValue)>,
+    ) {
+    }
+
+    fn g_from_dyn(
+        _variant: Option<&VariantMeta>,
+        _index: usize,
+        _fields: &mut Vec<(Key, DynValue)>,
+    ) -> Option<Self> {
+        Some(One)
+    }
+}
+
+impl<T: Dynamic, R: GDynamicFields> GDynamicFields for Product<T, R> {
+    fn g_to_dyn(
+        this: &Self::Ref<'_>,
+        variant: Option<&VariantMeta>,
+        index: usize,
+        fields: &mut Vec<(Key, DynValue)>,
+    ) {
+        let name = variant.and_then(|v| v.fields.name(index));
+        fields.push((Key::new(name, index), this.0.to_dyn()));
+        R::g_to_dyn(&this.1, variant, index + 1, fields);
+    }
+
+    fn g_from_dyn(
+        variant: Option<&VariantMeta>,
+        index: usize,
+        fields: &mut Vec<(Key, DynValue)>,
+    ) -> Option<Self> {
+        let name = variant.and_then(|v| v.fields.name(index));
+        let i = fields.iter().position(|(k, _)| k.matches(name, index))?;
+        let x = T::from_dyn(fields.remove(i).1)?;
+        Some(Product(x, R::g_from_dyn(variant, index + 1, fields)?))
+    }
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericDynamic`]，将会自动实现 [`Dynamic`]。
+///
+/// 有名字信息者，按其是否为 enum 转换成 [`DynValue::Variant`] 或 [`DynValue::Struct`]；
+/// 否则，仅有一个变体者视为 struct。
+///
+/// ```
+/// # use algtype::{Generic, dynamic::{Dynamic, DynValue, GenericDynamic, Key}};
+/// #[derive(Generic, Debug, PartialEq)]
+/// enum Shape {
+///     Circle { r: f64 },
+///     Empty,
+/// }
+///
+/// impl GenericDynamic for Shape {}
+///
+/// let v = Shape::Circle { r: 1.0 }.to_dyn();
+/// assert_eq!(
+///     v,
+///     DynValue::Variant {
+///         key: Key::Name("Circle".into()),
+///         fields: vec![(Key::Name("r".into()), DynValue::Float(1.0))],
+///     }
+/// );
+/// assert_eq!(Shape::from_dyn(v), Some(Shape::Circle { r: 1.0 }));
+///
+/// // 元组没有名字信息
+/// assert_eq!(
+///     (1u8,).to_dyn(),
+///     DynValue::Struct { fields: vec![(Key::Index(0), DynValue::UInt(1))] }
+/// );
+/// ```
+pub trait GenericDynamic: Generic
+where
+    Self::Repr: GDynamic,
+{
+}
+
+impl<T: GenericDynamic> Dynamic for T
+where
+    T::Repr: GDynamic,
+{
+    fn to_dyn(&self) -> DynValue {
+        let variants = T::META.map(|m| m.variants);
+        let mut fields = Vec::new();
+        let i = T::Repr::g_to_dyn(&self.as_repr(), variants, &mut fields);
+        if is_struct::<T>() {
+            DynValue::Struct { fields }
+        } else {
+            let name = variants.and_then(|v| v.get(i)).map(|v| v.name);
+            DynValue::Variant {
+                key: Key::new(name, i),
+                fields,
+            }
+        }
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        let variants = T::META.map(|m| m.variants);
+        let (index, mut fields) = match v {
+            DynValue::Struct { fields } if is_struct::<T>() => (0, fields),
+            DynValue::Variant { key, fields } if !is_struct::<T>() => {
+                let index = (0..T::Repr::COUNT).find(|&i| {
+                    let name = variants.and_then(|v| v.get(i)).map(|v| v.name);
+                    key.matches(name, i)
+                })?;
+                (index, fields)
+            }
+            _ => return None,
+        };
+        let repr = T::Repr::g_from_dyn(index, variants, &mut fields)?;
+        fields.is_empty().then(|| T::from_repr(repr))
+    }
+}
+
+fn is_struct<T: Generic>() -> bool
+where
+    T::Repr: GDynamic,
+{
+    T::META.map_or(T::Repr::COUNT == 1, |m| !m.is_enum)
+}
+
+// ADT
+
+impl<T: Dynamic> GenericDynamic for Option<T> {}
+
+impl<T: Dynamic, E: Dynamic> GenericDynamic for Result<T, E> {}
+
+// 数组
+
+impl<T, const N: usize> GenericDynamic for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GDynamic,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    () => {};
+    ($($tys:ident)*) => {
+        impl <$($tys: Dynamic),*> GenericDynamic for ($($tys,)*) {}
+    };
+}
+
+visit_tuple!(impl_tuple);
+
+// 基本类型
+
+impl Dynamic for () {
+    fn to_dyn(&self) -> DynValue {
+        DynValue::Unit
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        match v {
+            DynValue::Unit => Some(()),
+            _ => None,
+        }
+    }
+}
+
+impl Dynamic for bool {
+    fn to_dyn(&self) -> DynValue {
+        DynValue::Bool(*self)
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        match v {
+            DynValue::Bool(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_number {
+    ($($var:ident => $($ty:ty)*;)*) => {$($(
+        impl Dynamic for $ty {
+            fn to_dyn(&self) -> DynValue {
+                DynValue::$var((*self).into())
+            }
+
+            fn from_dyn(v: DynValue) -> Option<Self> {
+                match v {
+                    DynValue::Int(x) => x.try_into().ok(),
+                    DynValue::UInt(x) => x.try_into().ok(),
+                    _ => None,
+                }
+            }
+        }
+    )*)*};
+}
+
+impl_number!(
+    Int => i8 i16 i32 i64 i128;
+    UInt => u8 u16 u32 u64 u128;
+);
+
+impl Dynamic for isize {
+    fn to_dyn(&self) -> DynValue {
+        DynValue::Int(*self as i128)
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        i64::from_dyn(v)?.try_into().ok()
+    }
+}
+
+impl Dynamic for usize {
+    fn to_dyn(&self) -> DynValue {
+        DynValue::UInt(*self as u128)
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        u64::from_dyn(v)?.try_into().ok()
+    }
+}
+
+impl Dynamic for f64 {
+    fn to_dyn(&self) -> DynValue {
+        DynValue::Float(*self)
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        match v {
+            DynValue::Float(x) => Some(x),
+            DynValue::Int(x) => Some(x as f64),
+            DynValue::UInt(x) => Some(x as f64),
+            _ => None,
+        }
+    }
+}
+
+impl Dynamic for f32 {
+    fn to_dyn(&self) -> DynValue {
+        DynValue::Float((*self).into())
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        f64::from_dyn(v).map(|x| x as f32)
+    }
+}
+
+impl Dynamic for char {
+    fn to_dyn(&self) -> DynValue {
+        DynValue::Str((*self).into())
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        let DynValue::Str(s) = v else {
+            return None;
+        };
+        let mut chars = s.chars();
+        chars.next().filter(|_| chars.next().is_none())
+    }
+}
+
+impl Dynamic for String {
+    fn to_dyn(&self) -> DynValue {
+        DynValue::Str(self.clone())
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        match v {
+            DynValue::Str(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Dynamic> Dynamic for Vec<T> {
+    fn to_dyn(&self) -> DynValue {
+        DynValue::Seq(self.iter().map(T::to_dyn).collect())
+    }
+
+    fn from_dyn(v: DynValue) -> Option<Self> {
+        match v {
+            DynValue::Seq(x) => x.into_iter().map(T::from_dyn).collect(),
+            _ => None,
+        }
+    }
+}
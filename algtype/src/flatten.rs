@@ -0,0 +1,382 @@
+//! 展开嵌套的表示
+//!
+//! 若某字段是实现 [`Generic`](crate::Generic) 的类型，其表示会作为一个整体出现在外层的表示中。
+//! 在 derive 宏中，给字段加上 `#[algtype(flatten)]`，则会把其表示展开到外层：
+//!
+//! - 若变体只有这一个字段，则该字段的各变体依次代替此变体
+//! - 否则，该字段须只有一个变体，其各字段依次代替此字段
+//!
+//! 展开后的名字信息（[`Generic::META`](crate::Generic::META)）也随之展开：
+//! 代替变体者使用内层的变体名，代替字段者使用内层的字段名。
+//! 若内层没有名字信息，或其类型用到了外层的泛型参数，则为 `None`；
+//! 内层的字段没有名字时，外层的该变体视为匿名字段。
+//!
+//! ```
+//! # use algtype::{Generic, Sum, Product, One, Zero};
+//! #[derive(Generic, Debug, PartialEq)]
+//! enum Inner {
+//!     A,
+//!     B(bool),
+//! }
+//!
+//! #[derive(Generic, Debug, PartialEq)]
+//! struct Pair(u8, u8);
+//!
+//! #[derive(Generic, Debug, PartialEq)]
+//! enum Outer {
+//!     Inner(#[algtype(flatten)] Inner),
+//!     C(#[algtype(flatten)] Pair, i8),
+//! }
+//!
+//! let _: Sum<One, Sum<Product<bool, One>, Sum<Product<u8, Product<u8, Product<i8, One>>>, Zero>>> =
+//!     Outer::Inner(Inner::A).into_repr();
+//! assert_eq!(Outer::Inner(Inner::B(true)).into_repr(), Sum::Next(Sum::This(Product(true, One))));
+//! assert_eq!(Outer::from_repr(Sum::This(One)), Outer::Inner(Inner::A));
+//! ```
+//!
+//! 此模块的 trait 用于实现展开，一般无需直接使用。
+
+use crate::{FieldsMeta, Meta, One, Product, Repr, Sum, VariantMeta, Zero};
+
+/// 拼接和类型
+///
+/// `Self` 的各变体在前，`R` 的在后。
+pub trait Concat<R: Repr>: Repr + Sized {
+    /// 拼接的结果
+    type Output: Repr;
+
+    /// 从前者转换
+    fn inl(x: Self) -> Self::Output;
+    /// 从后者转换
+    fn inr(x: R) -> Self::Output;
+    /// 从前者的引用形式转换
+    fn inl_ref<'a>(x: Self::Ref<'a>) -> <Self::Output as Repr>::Ref<'a>
+    where
+        Self: 'a,
+        R: 'a;
+    /// 从后者的引用形式转换
+    fn inr_ref<'a>(x: R::Ref<'a>) -> <Self::Output as Repr>::Ref<'a>
+    where
+        Self: 'a,
+        R: 'a;
+    /// 从前者的可变引用形式转换
+    fn inl_mut<'a>(x: Self::Mut<'a>) -> <Self::Output as Repr>::Mut<'a>
+    where
+        Self: 'a,
+        R: 'a;
+    /// 从后者的可变引用形式转换
+    fn inr_mut<'a>(x: R::Mut<'a>) -> <Self::Output as Repr>::Mut<'a>
+    where
+        Self: 'a,
+        R: 'a;
+    /// 拆分为前者或后者
+    fn split(x: Self::Output) -> Sum<Self, R>;
+}
+
+impl<R: Repr> Concat<R> for Zero {
+    type Output = R;
+
+    fn inl(x: Self) -> Self::Output {
+        match x {}
+    }
+
+    fn inr(x: R) -> Self::Output {
+        x
+    }
+
+    fn inl_ref<'a>(x: Self::Ref<'a>) -> <Self::Output as Repr>::Ref<'a>
+    where
+        R: 'a,
+    {
+        match x {}
+    }
+
+    fn inr_ref<'a>(x: R::Ref<'a>) -> <Self::Output as Repr>::Ref<'a>
+    where
+        R: 'a,
+    {
+        x
+    }
+
+    fn inl_mut<'a>(x: Self::Mut<'a>) -> <Self::Output as Repr>::Mut<'a>
+    where
+        R: 'a,
+    {
+        match x {}
+    }
+
+    fn inr_mut<'a>(x: R::Mut<'a>) -> <Self::Output as Repr>::Mut<'a>
+    where
+        R: 'a,
+    {
+        x
+    }
+
+    fn split(x: Self::Output) -> Sum<Self, R> {
+        Sum::Next(x)
+    }
+}
+
+impl<T: Repr, S: Concat<R>, R: Repr> Concat<R> for Sum<T, S> {
+    type Output = Sum<T, S::Output>;
+
+    fn inl(x: Self) -> Self::Output {
+        match x {
+            Sum::This(x) => Sum::This(x),
+            Sum::Next(x) => Sum::Next(S::inl(x)),
+        }
+    }
+
+    fn inr(x: R) -> Self::Output {
+        Sum::Next(S::inr(x))
+    }
+
+    fn inl_ref<'a>(x: Self::Ref<'a>) -> <Self::Output as Repr>::Ref<'a>
+    where
+        Self: 'a,
+        R: 'a,
+    {
+        match x {
+            Sum::This(x) => Sum::This(x),
+            Sum::Next(x) => Sum::Next(S::inl_ref(x)),
+        }
+    }
+
+    fn inr_ref<'a>(x: R::Ref<'a>) -> <Self::Output as Repr>::Ref<'a>
+    where
+        Self: 'a,
+        R: 'a,
+    {
+        Sum::Next(S::inr_ref(x))
+    }
+
+    fn inl_mut<'a>(x: Self::Mut<'a>) -> <Self::Output as Repr>::Mut<'a>
+    where
+        Self: 'a,
+        R: 'a,
+    {
+        match x {
+            Sum::This(x) => Sum::This(x),
+            Sum::Next(x) => Sum::Next(S::inl_mut(x)),
+        }
+    }
+
+    fn inr_mut<'a>(x: R::Mut<'a>) -> <Self::Output as Repr>::Mut<'a>
+    where
+        Self: 'a,
+        R: 'a,
+    {
+        Sum::Next(S::inr_mut(x))
+    }
+
+    fn split(x: Self::Output) -> Sum<Self, R> {
+        match x {
+            Sum::This(x) => Sum::This(Sum::This(x)),
+            Sum::Next(x) => match S::split(x) {
+                Sum::This(x) => Sum::This(Sum::Next(x)),
+                Sum::Next(x) => Sum::Next(x),
+            },
+        }
+    }
+}
+
+/// 拼接积类型
+///
+/// `Self` 的各字段在前，`R` 的在后。
+pub trait Append<R: Repr>: Repr + Sized {
+    /// 拼接的结果
+    type Output: Repr;
+
+    /// 拼接
+    fn append(x: Self, r: R) -> Self::Output;
+    /// 拼接引用形式
+    fn append_ref<'a>(x: Self::Ref<'a>, r: R::Ref<'a>) -> <Self::Output as Repr>::Ref<'a>
+    where
+        Self: 'a,
+        R: 'a;
+    /// 拼接可变引用形式
+    fn append_mut<'a>(x: Self::Mut<'a>, r: R::Mut<'a>) -> <Self::Output as Repr>::Mut<'a>
+    where
+        Self: 'a,
+        R: 'a;
+    /// 拆分为前者和后者
+    fn split(x: Self::Output) -> (Self, R);
+}
+
+impl<R: Repr> Append<R> for One {
+    type Output = R;
+
+    fn append(_x: Self, r: R) -> Self::Output {
+        r
+    }
+
+    fn append_ref<'a>(_x: Self::Ref<'a>, r: R::Ref<'a>) -> <Self::Output as Repr>::Ref<'a>
+    where
+        R: 'a,
+    {
+        r
+    }
+
+    fn append_mut<'a>(_x: Self::Mut<'a>, r: R::Mut<'a>) -> <Self::Output as Repr>::Mut<'a>
+    where
+        R: 'a,
+    {
+        r
+    }
+
+    fn split(x: Self::Output) -> (Self, R) {
+        (One, x)
+    }
+}
+
+impl<T, S: Append<R>, R: Repr> Append<R> for Product<T, S> {
+    type Output = Product<T, S::Output>;
+
+    fn append(x: Self, r: R) -> Self::Output {
+        Product(x.0, S::append(x.1, r))
+    }
+
+    fn append_ref<'a>(x: Self::Ref<'a>, r: R::Ref<'a>) -> <Self::Output as Repr>::Ref<'a>
+    where
+        Self: 'a,
+        R: 'a,
+    {
+        Product(x.0, S::append_ref(x.1, r))
+    }
+
+    fn append_mut<'a>(x: Self::Mut<'a>, r: R::Mut<'a>) -> <Self::Output as Repr>::Mut<'a>
+    where
+        Self: 'a,
+        R: 'a,
+    {
+        Product(x.0, S::append_mut(x.1, r))
+    }
+
+    fn split(x: Self::Output) -> (Self, R) {
+        let (s, r) = S::split(x.1);
+        (Product(x.0, s), r)
+    }
+}
+
+/// 只有一个变体的和类型
+pub trait Single: Repr + Sized {
+    /// 唯一的变体
+    type Product: Repr;
+
+    /// 取出唯一的变体
+    fn unwrap(x: Self) -> Self::Product;
+    /// 取出唯一的变体的引用形式
+    fn unwrap_ref<'a>(x: Self::Ref<'a>) -> <Self::Product as Repr>::Ref<'a>
+    where
+        Self: 'a;
+    /// 取出唯一的变体的可变引用形式
+    fn unwrap_mut<'a>(x: Self::Mut<'a>) -> <Self::Product as Repr>::Mut<'a>
+    where
+        Self: 'a;
+    /// 从唯一的变体转换
+    fn wrap(x: Self::Product) -> Self;
+}
+
+impl<P: Repr> Single for Sum<P, Zero> {
+    type Product = P;
+
+    fn unwrap(x: Self) -> Self::Product {
+        match x {
+            Sum::This(x) => x,
+            Sum::Next(x) => match x {},
+        }
+    }
+
+    fn unwrap_ref<'a>(x: Self::Ref<'a>) -> <Self::Product as Repr>::Ref<'a>
+    where
+        Self: 'a,
+    {
+        match x {
+            Sum::This(x) => x,
+            Sum::Next(x) => match x {},
+        }
+    }
+
+    fn unwrap_mut<'a>(x: Self::Mut<'a>) -> <Self::Product as Repr>::Mut<'a>
+    where
+        Self: 'a,
+    {
+        match x {
+            Sum::This(x) => x,
+            Sum::Next(x) => match x {},
+        }
+    }
+
+    fn wrap(x: Self::Product) -> Self {
+        Sum::This(x)
+    }
+}
+
+// 以下用于 derive 宏在编译期拼接名字信息
+
+/// 内层的各变体
+#[doc(hidden)]
+pub const fn meta_variants(meta: Option<&'static Meta>) -> Option<&'static [VariantMeta]> {
+    match meta {
+        Some(meta) => Some(meta.variants),
+        None => None,
+    }
+}
+
+/// 内层唯一的变体的字段名，无字段时为空
+#[doc(hidden)]
+pub const fn meta_fields(meta: Option<&'static Meta>) -> Option<&'static [&'static str]> {
+    match meta {
+        Some(Meta { variants: [v], .. }) => match v.fields {
+            FieldsMeta::Named(names) => Some(names),
+            FieldsMeta::Unit => Some(&[]),
+            FieldsMeta::Unnamed => None,
+        },
+        _ => None,
+    }
+}
+
+/// 各部分是否都有
+#[doc(hidden)]
+pub const fn all_some<T>(parts: &[Option<&[T]>]) -> bool {
+    let mut i = 0;
+    while i < parts.len() {
+        if parts[i].is_none() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// 各部分的总长度
+#[doc(hidden)]
+pub const fn total_len<T>(parts: &[Option<&[T]>]) -> usize {
+    let mut n = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        if let Some(part) = parts[i] {
+            n += part.len();
+        }
+        i += 1;
+    }
+    n
+}
+
+/// 拼接各部分，`N` 须为 [`total_len`]
+#[doc(hidden)]
+pub const fn concat<T: Copy, const N: usize>(parts: &[Option<&[T]>], fill: T) -> [T; N] {
+    let mut out = [fill; N];
+    let (mut i, mut k) = (0, 0);
+    while i < parts.len() {
+        if let Some(part) = parts[i] {
+            let mut j = 0;
+            while j < part.len() {
+                out[k] = part[j];
+                (j, k) = (j + 1, k + 1);
+            }
+        }
+        i += 1;
+    }
+    out
+}
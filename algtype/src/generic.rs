@@ -100,6 +100,57 @@ impl<T, R: Repr> Repr for Product<T, R> {
     }
 }
 
+/// 类型的名字信息
+///
+/// 参见 [`Generic::META`]。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Meta {
+    /// 类型的名字
+    pub name: &'static str,
+    /// 是否为 enum
+    pub is_enum: bool,
+    /// 各变体的信息，顺序与表示一致
+    ///
+    /// struct 只有一个变体，其名字与类型相同。
+    pub variants: &'static [VariantMeta],
+}
+
+/// 变体的名字信息
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VariantMeta {
+    /// 变体的名字
+    pub name: &'static str,
+    /// 字段的名字
+    pub fields: FieldsMeta,
+}
+
+/// 字段的名字信息
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FieldsMeta {
+    /// 具名字段，如 `{ a: i32 }`，包含各字段的名字
+    Named(&'static [&'static str]),
+    /// 匿名字段，如 `(i32)`
+    Unnamed,
+    /// 无字段，如 `A`
+    Unit,
+}
+
+impl FieldsMeta {
+    /// 某位置的字段的名字
+    ///
+    /// ```
+    /// # use algtype::FieldsMeta;
+    /// assert_eq!(FieldsMeta::Named(&["a", "b"]).name(1), Some("b"));
+    /// assert_eq!(FieldsMeta::Unnamed.name(0), None);
+    /// ```
+    pub fn name(&self, index: usize) -> Option<&'static str> {
+        match self {
+            FieldsMeta::Named(names) => names.get(index).copied(),
+            FieldsMeta::Unnamed | FieldsMeta::Unit => None,
+        }
+    }
+}
+
 /// 类型与其表示的互转
 ///
 /// [`Generic::Repr`] 提供了类型的*表示*，即其数据的结构。
@@ -114,6 +165,7 @@ impl<T, R: Repr> Repr for Product<T, R> {
 ///
 /// 类型的表示，是其底层类型的积的和，呈现为类型层面的列表。
 /// 它只体现结构，不包含其他信息，例如字段或变体的名字、枚举的值之类。
+/// 名字之类的信息另由 [`Generic::META`] 提供。
 /// 表示的伪代码如下：
 ///
 /// ```txt
@@ -142,6 +194,24 @@ impl<T, R: Repr> Repr for Product<T, R> {
 pub trait Generic {
     /// 类型的表示
     type Repr: Repr;
+    /// 类型的名字信息
+    ///
+    /// derive 宏会提供之，而手写的实现默认没有。
+    ///
+    /// ```
+    /// # use algtype::{Generic, FieldsMeta};
+    /// #[derive(Generic)]
+    /// struct S {
+    ///     a: i32,
+    /// }
+    ///
+    /// let meta = S::META.unwrap();
+    /// assert_eq!(meta.name, "S");
+    /// assert_eq!(meta.variants[0].fields, FieldsMeta::Named(&["a"]));
+    /// assert_eq!(Option::<()>::META.unwrap().variants[1].name, "Some");
+    /// assert_eq!(<(i32,)>::META, None);
+    /// ```
+    const META: Option<&'static Meta> = None;
     /// 把数据转换成其表示
     fn into_repr(self) -> Self::Repr;
     /// 从数据的表示转换成数据
@@ -0,0 +1,249 @@
+//! 堆内存的统计
+//!
+//! [`HeapSize`] 统计数据（间接）拥有的堆内存，不含数据本身，
+//! [`total_size`] 则加上数据本身的大小。可用于估计缓存的内存占用。
+//!
+//! 统计的是分配的容量，不含分配器的额外开销，故只是估计。
+//! 与其他基于 [`Generic`] 的功能一样，递归的类型无法自动实现，可用 [`generic_heap_size`] 手动实现。
+
+use crate::{visit_tuple, Generic, One, Product, Repr, Sum, Zero};
+use core::mem::size_of_val;
+
+/// 可以统计堆内存者
+///
+/// 建议使用 [`GenericHeapSize`] 自动实现。
+pub trait HeapSize {
+    /// 拥有的堆内存的字节数，不含自身的大小
+    fn heap_size(&self) -> usize;
+}
+
+/// 数据本身及其拥有的堆内存的字节数
+///
+/// ```
+/// # use algtype::heap_size::total_size;
+/// assert_eq!(total_size(&1u32), 4);
+/// assert_eq!(total_size(&Some(1u8)), 2);
+/// ```
+pub fn total_size<T: HeapSize + ?Sized>(x: &T) -> usize {
+    size_of_val(x) + x.heap_size()
+}
+
+/// 表示上的 [`HeapSize`]
+pub trait GHeapSize: Repr {
+    fn g_heap_size(this: &Self::Ref<'_>) -> usize;
+}
+
+impl GHeapSize for Zero {
+    fn g_heap_size(this: &Self::Ref<'_>) -> usize {
+        match *this {}
+    }
+}
+
+impl GHeapSize for One {
+    fn g_heap_size(_this: &Self::Ref<'_>) -> usize {
+        0
+    }
+}
+
+impl<T: GHeapSize, R: GHeapSize> GHeapSize for Sum<T, R> {
+    fn g_heap_size(this: &Self::Ref<'_>) -> usize {
+        match this {
+            Sum::This(x) => T::g_heap_size(x),
+            Sum::Next(x) => R::g_heap_size(x),
+        }
+    }
+}
+
+impl<T: HeapSize, R: GHeapSize> GHeapSize for Product<T, R> {
+    fn g_heap_size(this: &Self::Ref<'_>) -> usize {
+        this.0.heap_size() + R::g_heap_size(&this.1)
+    }
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericHeapSize`]，将会自动实现 [`HeapSize`]，
+/// 其结果为当前变体各字段的结果之和，再加上 [`own_heap_size`](GenericHeapSize::own_heap_size)。
+///
+/// ```
+/// # use algtype::{Generic, heap_size::{HeapSize, GenericHeapSize}};
+/// #[derive(Generic)]
+/// struct Entry {
+///     key: String,
+///     value: Vec<u32>,
+/// }
+///
+/// impl GenericHeapSize for Entry {}
+///
+/// let mut value = Vec::with_capacity(4);
+/// value.push(1);
+/// let e = Entry { key: "abc".into(), value };
+/// assert_eq!(e.heap_size(), 3 + 16);
+/// ```
+pub trait GenericHeapSize: Generic
+where
+    Self::Repr: GHeapSize,
+{
+    /// 自身直接拥有的、不属于任何字段的堆内存，默认为零
+    ///
+    /// 如 `Box` 所指的内存。
+    fn own_heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: GenericHeapSize> HeapSize for T
+where
+    T::Repr: GHeapSize,
+{
+    fn heap_size(&self) -> usize {
+        self.own_heap_size() + generic_heap_size(self)
+    }
+}
+
+/// 基于 [`Generic`] 的 [`HeapSize::heap_size`]，即当前变体各字段的结果之和
+///
+/// 用于手动实现递归的类型：
+///
+/// ```
+/// # use algtype::{Generic, heap_size::{self, HeapSize}};
+/// #[derive(Generic)]
+/// struct List {
+///     value: u32,
+///     next: Option<Box<List>>,
+/// }
+///
+/// impl HeapSize for List {
+///     fn heap_size(&self) -> usize {
+///         heap_size::generic_heap_size(self)
+///     }
+/// }
+///
+/// let list = List { value: 1, next: Some(Box::new(List { value: 2, next: None })) };
+/// assert_eq!(list.heap_size(), size_of::<List>());
+/// ```
+pub fn generic_heap_size<T: Generic>(x: &T) -> usize
+where
+    T::Repr: GHeapSize,
+{
+    T::Repr::g_heap_size(&x.as_repr())
+}
+
+// ADT
+
+impl<T: HeapSize> GenericHeapSize for Option<T> {}
+
+impl<T: HeapSize, E: HeapSize> GenericHeapSize for Result<T, E> {}
+
+impl GenericHeapSize for bool {}
+
+// 数组
+
+impl<T, const N: usize> GenericHeapSize for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GHeapSize,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    ($($tys:ident)*) => {
+        impl <$($tys: HeapSize),*> GenericHeapSize for ($($tys,)*) {}
+    };
+}
+
+visit_tuple!(impl_tuple);
+
+// 不拥有堆内存者
+
+macro_rules! impl_plain {
+    ($($ty:ty)*) => {$(
+        impl HeapSize for $ty {
+            #[inline]
+            fn heap_size(&self) -> usize {
+                0
+            }
+        }
+    )*};
+}
+
+impl_plain!(
+    u8 u16 u32 u64 u128 usize
+    i8 i16 i32 i64 i128 isize
+    f32 f64 char &str
+);
+
+impl<T> HeapSize for &[T] {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: HeapSize> HeapSize for [T] {
+    fn heap_size(&self) -> usize {
+        self.iter().map(T::heap_size).sum()
+    }
+}
+
+// 需要堆分配者
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::{GenericHeapSize, HeapSize};
+    use alloc::{
+        boxed::Box,
+        collections::{BTreeMap, BTreeSet},
+        string::String,
+        vec::Vec,
+    };
+    use core::mem::{size_of, size_of_val};
+
+    impl<T: HeapSize> GenericHeapSize for Box<T> {
+        fn own_heap_size(&self) -> usize {
+            size_of_val(&**self)
+        }
+    }
+
+    impl HeapSize for Box<str> {
+        fn heap_size(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<T: HeapSize> HeapSize for Box<[T]> {
+        fn heap_size(&self) -> usize {
+            size_of_val(&**self) + (**self).heap_size()
+        }
+    }
+
+    impl HeapSize for String {
+        fn heap_size(&self) -> usize {
+            self.capacity()
+        }
+    }
+
+    impl<T: HeapSize> HeapSize for Vec<T> {
+        fn heap_size(&self) -> usize {
+            self.capacity() * size_of::<T>() + self[..].heap_size()
+        }
+    }
+
+    /// 节点的开销未计入
+    impl<K: HeapSize, V: HeapSize> HeapSize for BTreeMap<K, V> {
+        fn heap_size(&self) -> usize {
+            self.iter()
+                .map(|(k, v)| size_of::<(K, V)>() + k.heap_size() + v.heap_size())
+                .sum()
+        }
+    }
+
+    /// 节点的开销未计入
+    impl<T: HeapSize> HeapSize for BTreeSet<T> {
+        fn heap_size(&self) -> usize {
+            self.iter().map(|x| size_of::<T>() + x.heap_size()).sum()
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! 异构列表
+//!
+//! 以 [`One`] 结尾的 [`Product`] 就是异构列表（HList），此模块提供其上的操作。
+//! 它也是只有一个变体的类型（如元组）的表示中的积类型，因此可与元组互相转换。
+//!
+//! ```
+//! # use algtype::{hlist::{self, HList, Poly}, index::I2, One, Product};
+//! struct Show;
+//!
+//! impl<X: ToString> Poly<X> for Show {
+//!     type Output = String;
+//!     fn call(&mut self, x: X) -> String {
+//!         x.to_string()
+//!     }
+//! }
+//!
+//! let l = hlist::from_tuple((1, 'a', true));
+//! assert_eq!(l.len(), 3);
+//! assert_eq!(l.reverse().into_tuple::<(_, _, _)>(), (true, 'a', 1));
+//! assert_eq!(l.map(Show).into_tuple::<(_, _, _)>(), ("1".into(), "a".into(), "true".into()));
+//! let (a, b) = l.append(Product(2.5, One)).split_at::<I2>();
+//! assert_eq!((a.into_tuple(), b.into_tuple()), ((1, 'a'), (true, 2.5)));
+//! ```
+
+use crate::{
+    flatten::{Append, Single},
+    index::{Here, There},
+    Generic, One, Product, Repr,
+};
+
+/// 异构列表
+pub trait HList: Repr + Sized {
+    /// 长度
+    const LEN: usize;
+
+    /// 长度
+    fn len(&self) -> usize {
+        Self::LEN
+    }
+
+    /// 是否为空
+    fn is_empty(&self) -> bool {
+        Self::LEN == 0
+    }
+
+    /// 拼接，`self` 在前
+    fn append<R: HList>(self, r: R) -> Self::Output
+    where
+        Self: Append<R>,
+    {
+        Append::append(self, r)
+    }
+
+    /// 反转
+    fn reverse(self) -> Self::Output
+    where
+        Self: ReverseInto<One>,
+    {
+        self.reverse_into(One)
+    }
+
+    /// 拆分为前 `I` 个和其余的，`I` 见 [`index`](crate::index)
+    fn split_at<I>(self) -> (Self::Left, Self::Right)
+    where
+        Self: SplitAt<I>,
+    {
+        SplitAt::split(self)
+    }
+
+    /// 对每个元素调用多态函数
+    fn map<F>(self, mut f: F) -> Self::Output
+    where
+        Self: Map<F>,
+    {
+        self.map_with(&mut f)
+    }
+
+    /// 逐个配对
+    fn zip<R: HList>(self, r: R) -> Self::Output
+    where
+        Self: Zip<R>,
+    {
+        Zip::zip(self, r)
+    }
+
+    /// 逐个以多态函数合并
+    fn zip_with<R: HList, F>(self, r: R, mut f: F) -> Self::Output
+    where
+        Self: ZipWith<R, F>,
+    {
+        self.zip_by(r, &mut f)
+    }
+
+    /// 转换成表示中的积类型为此列表的类型，如元组
+    fn into_tuple<T: Generic>(self) -> T
+    where
+        T::Repr: Single<Product = Self>,
+    {
+        T::from_repr(Single::wrap(self))
+    }
+}
+
+impl HList for One {
+    const LEN: usize = 0;
+}
+
+impl<X, R: HList> HList for Product<X, R> {
+    const LEN: usize = R::LEN + 1;
+}
+
+/// 转换成表示中的积类型，如把元组转换成列表
+pub fn from_tuple<T: Generic>(x: T) -> <T::Repr as Single>::Product
+where
+    T::Repr: Single,
+{
+    Single::unwrap(x.into_repr())
+}
+
+/// 反转后拼接到 `Acc` 之前
+pub trait ReverseInto<Acc> {
+    /// 结果
+    type Output;
+    /// 反转后拼接到 `acc` 之前
+    fn reverse_into(self, acc: Acc) -> Self::Output;
+}
+
+impl<Acc> ReverseInto<Acc> for One {
+    type Output = Acc;
+
+    fn reverse_into(self, acc: Acc) -> Acc {
+        acc
+    }
+}
+
+impl<X, R: ReverseInto<Product<X, Acc>>, Acc> ReverseInto<Acc> for Product<X, R> {
+    type Output = R::Output;
+
+    fn reverse_into(self, acc: Acc) -> Self::Output {
+        self.1.reverse_into(Product(self.0, acc))
+    }
+}
+
+/// 在位置 `I` 处拆分
+pub trait SplitAt<I> {
+    /// 前 `I` 个
+    type Left;
+    /// 其余的
+    type Right;
+    /// 拆分
+    fn split(self) -> (Self::Left, Self::Right);
+}
+
+impl<L: HList> SplitAt<Here> for L {
+    type Left = One;
+    type Right = L;
+
+    fn split(self) -> (One, L) {
+        (One, self)
+    }
+}
+
+impl<X, R: SplitAt<I>, I> SplitAt<There<I>> for Product<X, R> {
+    type Left = Product<X, R::Left>;
+    type Right = R::Right;
+
+    fn split(self) -> (Self::Left, Self::Right) {
+        let (l, r) = self.1.split();
+        (Product(self.0, l), r)
+    }
+}
+
+/// 多态函数，对不同类型的参数有不同类型的结果
+pub trait Poly<X> {
+    /// 结果
+    type Output;
+    /// 调用
+    fn call(&mut self, x: X) -> Self::Output;
+}
+
+/// 对每个元素调用多态函数 `F`
+pub trait Map<F> {
+    /// 结果
+    type Output;
+    /// 依次调用
+    fn map_with(self, f: &mut F) -> Self::Output;
+}
+
+impl<F> Map<F> for One {
+    type Output = One;
+
+    fn map_with(self, _f: &mut F) -> One {
+        One
+    }
+}
+
+impl<X, R: Map<F>, F: Poly<X>> Map<F> for Product<X, R> {
+    type Output = Product<F::Output, R::Output>;
+
+    fn map_with(self, f: &mut F) -> Self::Output {
+        let x = f.call(self.0);
+        Product(x, self.1.map_with(f))
+    }
+}
+
+/// 与 `R` 逐个配对
+pub trait Zip<R> {
+    /// 结果
+    type Output;
+    /// 配对
+    fn zip(self, r: R) -> Self::Output;
+}
+
+impl Zip<One> for One {
+    type Output = One;
+
+    fn zip(self, _r: One) -> One {
+        One
+    }
+}
+
+impl<X, R: Zip<S>, Y, S> Zip<Product<Y, S>> for Product<X, R> {
+    type Output = Product<(X, Y), R::Output>;
+
+    fn zip(self, r: Product<Y, S>) -> Self::Output {
+        Product((self.0, r.0), self.1.zip(r.1))
+    }
+}
+
+/// 与 `R` 逐个以多态函数 `F` 合并，`F` 的参数是一对元素
+pub trait ZipWith<R, F> {
+    /// 结果
+    type Output;
+    /// 依次调用
+    fn zip_by(self, r: R, f: &mut F) -> Self::Output;
+}
+
+impl<F> ZipWith<One, F> for One {
+    type Output = One;
+
+    fn zip_by(self, _r: One, _f: &mut F) -> One {
+        One
+    }
+}
+
+impl<X, R: ZipWith<S, F>, Y, S, F: Poly<(X, Y)>> ZipWith<Product<Y, S>, F> for Product<X, R> {
+    type Output = Product<F::Output, R::Output>;
+
+    fn zip_by(self, r: Product<Y, S>, f: &mut F) -> Self::Output {
+        let x = f.call((self.0, r.0));
+        Product(x, self.1.zip_by(r.1, f))
+    }
+}
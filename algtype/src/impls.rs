@@ -1,8 +1,22 @@
-use crate::{Generic, One, Product, Repr, Sum, Zero};
+use crate::{FieldsMeta, Generic, Meta, One, Product, Repr, Sum, VariantMeta, Zero};
 use algtype_derive::impl_generic;
 
 impl Generic for bool {
     type Repr = Sum<One, Sum<One, Zero>>;
+    const META: Option<&'static Meta> = Some(&Meta {
+        name: "bool",
+        is_enum: true,
+        variants: &[
+            VariantMeta {
+                name: "false",
+                fields: FieldsMeta::Unit,
+            },
+            VariantMeta {
+                name: "true",
+                fields: FieldsMeta::Unit,
+            },
+        ],
+    });
 
     #[inline]
     fn into_repr(self) -> Self::Repr {
@@ -33,6 +47,56 @@ impl Generic for bool {
     }
 }
 
+// 表示本身
+
+impl Generic for Zero {
+    type Repr = Self;
+
+    #[inline]
+    fn into_repr(self) -> Self::Repr {
+        self
+    }
+
+    #[inline]
+    fn from_repr(repr: Self::Repr) -> Self {
+        repr
+    }
+
+    #[inline]
+    fn as_repr(&self) -> <Self::Repr as Repr>::Ref<'_> {
+        self.as_ref()
+    }
+
+    #[inline]
+    fn as_mut_repr(&mut self) -> <Self::Repr as Repr>::Mut<'_> {
+        self.as_mut_ref()
+    }
+}
+
+impl<T: Repr, R: Repr> Generic for Sum<T, R> {
+    type Repr = Self;
+
+    #[inline]
+    fn into_repr(self) -> Self::Repr {
+        self
+    }
+
+    #[inline]
+    fn from_repr(repr: Self::Repr) -> Self {
+        repr
+    }
+
+    #[inline]
+    fn as_repr(&self) -> <Self::Repr as Repr>::Ref<'_> {
+        self.as_ref()
+    }
+
+    #[inline]
+    fn as_mut_repr(&mut self) -> <Self::Repr as Repr>::Mut<'_> {
+        self.as_mut_ref()
+    }
+}
+
 // ADT
 
 impl_generic!(
@@ -173,3 +237,33 @@ macro_rules! impl_array {
 }
 
 impl_array!(@many, 0 x0 1 x1 2 x2 3 x3 4 x4 5 x5 6 x6 7 x7 8 x8 9 x9 10 x10 11 x11 12);
+
+// 指针
+
+#[cfg(feature = "alloc")]
+impl<T> Generic for alloc::boxed::Box<T> {
+    type Repr = Sum<Product<T, One>, Zero>;
+
+    #[inline]
+    fn into_repr(self) -> Self::Repr {
+        Sum::This(Product(*self, One))
+    }
+
+    #[inline]
+    fn from_repr(repr: Self::Repr) -> Self {
+        match repr {
+            Sum::This(Product(x, One)) => alloc::boxed::Box::new(x),
+            Sum::Next(a) => match a {},
+        }
+    }
+
+    #[inline]
+    fn as_repr(&self) -> <Self::Repr as Repr>::Ref<'_> {
+        Sum::This(Product(&**self, One))
+    }
+
+    #[inline]
+    fn as_mut_repr(&mut self) -> <Self::Repr as Repr>::Mut<'_> {
+        Sum::This(Product(&mut **self, One))
+    }
+}
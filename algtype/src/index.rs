@@ -0,0 +1,39 @@
+//! 类型层面的位置
+//!
+//! 用于在表示中按类型查找时，指明所找到的位置。一般由类型推导得出，无需手写。
+//! 若有多个位置符合，则无法推导，会编译错误。需要手写时，可用 [`I0`]、[`I1`] 等别名。
+
+use core::marker::PhantomData;
+
+/// 第一个位置
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Here;
+
+/// `I` 的下一个位置
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct There<I>(PhantomData<I>);
+
+/// 第 0 个位置
+pub type I0 = Here;
+/// 第 1 个位置
+pub type I1 = There<I0>;
+/// 第 2 个位置
+pub type I2 = There<I1>;
+/// 第 3 个位置
+pub type I3 = There<I2>;
+/// 第 4 个位置
+pub type I4 = There<I3>;
+/// 第 5 个位置
+pub type I5 = There<I4>;
+/// 第 6 个位置
+pub type I6 = There<I5>;
+/// 第 7 个位置
+pub type I7 = There<I6>;
+/// 第 8 个位置
+pub type I8 = There<I7>;
+/// 第 9 个位置
+pub type I9 = There<I8>;
+/// 第 10 个位置
+pub type I10 = There<I9>;
+/// 第 11 个位置
+pub type I11 = There<I10>;
@@ -0,0 +1,370 @@
+//! 表示间的同构
+//!
+//! [`Iso<A, B>`] 是 `A` 与 `B` 之间互逆的一对转换。同构可以组合（[`Iso::then`]）和取逆（[`Iso::inverse`]）。
+//! 此模块提供了表示上的常用同构，如交换律、结合律、分配律、单位元，
+//! 以及作用于表示的一部分的 [`OnThis`]、[`OnNext`]、[`OnHead`]、[`OnTail`]。
+//!
+//! 结合 [`Generic`]，可以在字段或变体顺序不同的类型间转换，且转换的正确性由类型检查保证：
+//!
+//! ```
+//! # use algtype::{Generic, iso::{convert, OnThis, ProductSwap}};
+//! #[derive(Generic, Debug, PartialEq)]
+//! struct A {
+//!     x: u8,
+//!     y: bool,
+//! }
+//!
+//! #[derive(Generic, Debug, PartialEq)]
+//! struct B {
+//!     y: bool,
+//!     x: u8,
+//! }
+//!
+//! let b: B = convert(A { x: 1, y: true }, &OnThis(ProductSwap));
+//! assert_eq!(b, B { y: true, x: 1 });
+//! ```
+
+use crate::{Generic, One, Product, Sum, Zero};
+use core::marker::PhantomData;
+
+/// `A` 与 `B` 间的同构
+///
+/// 须满足 `from(to(a)) == a` 且 `to(from(b)) == b`。
+pub trait Iso<A, B> {
+    /// 正向转换
+    fn to(&self, a: A) -> B;
+    /// 反向转换
+    fn from(&self, b: B) -> A;
+
+    /// 先应用此同构，再应用 `j`
+    fn then<C, J: Iso<B, C>>(self, j: J) -> Then<Self, J, B>
+    where
+        Self: Sized,
+    {
+        Then(self, j, PhantomData)
+    }
+
+    /// 逆同构
+    fn inverse(self) -> Inverse<Self>
+    where
+        Self: Sized,
+    {
+        Inverse(self)
+    }
+}
+
+/// 依据 `iso` 转换表示同构的类型
+///
+/// ```
+/// # use algtype::iso::{convert, SumSwap};
+/// // Result<T, E> 的表示是 Sum<Product<E, One>, Sum<Product<T, One>, Zero>>
+/// let x: Result<u8, i8> = convert(Ok::<i8, u8>(1), &SumSwap);
+/// assert_eq!(x, Err(1));
+/// ```
+pub fn convert<T: Generic, U: Generic>(x: T, iso: &impl Iso<T::Repr, U::Repr>) -> U {
+    U::from_repr(iso.to(x.into_repr()))
+}
+
+/// 同构的组合，见 [`Iso::then`]
+///
+/// `B` 为中间的类型。
+pub struct Then<I, J, B>(pub I, pub J, PhantomData<fn(B) -> B>);
+
+impl<A, B, C, I: Iso<A, B>, J: Iso<B, C>> Iso<A, C> for Then<I, J, B> {
+    fn to(&self, a: A) -> C {
+        self.1.to(self.0.to(a))
+    }
+
+    fn from(&self, c: C) -> A {
+        self.0.from(self.1.from(c))
+    }
+}
+
+/// 逆同构，见 [`Iso::inverse`]
+pub struct Inverse<I>(pub I);
+
+impl<A, B, I: Iso<A, B>> Iso<B, A> for Inverse<I> {
+    fn to(&self, b: B) -> A {
+        self.0.from(b)
+    }
+
+    fn from(&self, a: A) -> B {
+        self.0.to(a)
+    }
+}
+
+/// 恒等
+pub struct Refl;
+
+impl<A> Iso<A, A> for Refl {
+    fn to(&self, a: A) -> A {
+        a
+    }
+
+    fn from(&self, a: A) -> A {
+        a
+    }
+}
+
+/// 类型与其表示
+pub struct Gen<T>(PhantomData<fn(T) -> T>);
+
+impl<T> Gen<T> {
+    /// 创建同构
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for Gen<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Generic> Iso<T, T::Repr> for Gen<T> {
+    fn to(&self, a: T) -> T::Repr {
+        a.into_repr()
+    }
+
+    fn from(&self, b: T::Repr) -> T {
+        T::from_repr(b)
+    }
+}
+
+// 作用于一部分
+
+/// 作用于和类型的前者
+pub struct OnThis<I>(pub I);
+
+impl<A, B, R, I: Iso<A, B>> Iso<Sum<A, R>, Sum<B, R>> for OnThis<I> {
+    fn to(&self, a: Sum<A, R>) -> Sum<B, R> {
+        match a {
+            Sum::This(x) => Sum::This(self.0.to(x)),
+            Sum::Next(x) => Sum::Next(x),
+        }
+    }
+
+    fn from(&self, b: Sum<B, R>) -> Sum<A, R> {
+        match b {
+            Sum::This(x) => Sum::This(self.0.from(x)),
+            Sum::Next(x) => Sum::Next(x),
+        }
+    }
+}
+
+/// 作用于和类型的后者
+pub struct OnNext<I>(pub I);
+
+impl<T, A, B, I: Iso<A, B>> Iso<Sum<T, A>, Sum<T, B>> for OnNext<I> {
+    fn to(&self, a: Sum<T, A>) -> Sum<T, B> {
+        match a {
+            Sum::This(x) => Sum::This(x),
+            Sum::Next(x) => Sum::Next(self.0.to(x)),
+        }
+    }
+
+    fn from(&self, b: Sum<T, B>) -> Sum<T, A> {
+        match b {
+            Sum::This(x) => Sum::This(x),
+            Sum::Next(x) => Sum::Next(self.0.from(x)),
+        }
+    }
+}
+
+/// 作用于积类型的前者
+pub struct OnHead<I>(pub I);
+
+impl<A, B, R, I: Iso<A, B>> Iso<Product<A, R>, Product<B, R>> for OnHead<I> {
+    fn to(&self, Product(x, r): Product<A, R>) -> Product<B, R> {
+        Product(self.0.to(x), r)
+    }
+
+    fn from(&self, Product(x, r): Product<B, R>) -> Product<A, R> {
+        Product(self.0.from(x), r)
+    }
+}
+
+/// 作用于积类型的后者
+pub struct OnTail<I>(pub I);
+
+impl<T, A, B, I: Iso<A, B>> Iso<Product<T, A>, Product<T, B>> for OnTail<I> {
+    fn to(&self, Product(x, r): Product<T, A>) -> Product<T, B> {
+        Product(x, self.0.to(r))
+    }
+
+    fn from(&self, Product(x, r): Product<T, B>) -> Product<T, A> {
+        Product(x, self.0.from(r))
+    }
+}
+
+// 交换律
+
+/// `A + B ≅ B + A`
+pub struct SumComm;
+
+impl<A, B> Iso<Sum<A, B>, Sum<B, A>> for SumComm {
+    fn to(&self, a: Sum<A, B>) -> Sum<B, A> {
+        match a {
+            Sum::This(x) => Sum::Next(x),
+            Sum::Next(x) => Sum::This(x),
+        }
+    }
+
+    fn from(&self, b: Sum<B, A>) -> Sum<A, B> {
+        self.to(b)
+    }
+}
+
+/// `A × B ≅ B × A`
+pub struct ProductComm;
+
+impl<A, B> Iso<Product<A, B>, Product<B, A>> for ProductComm {
+    fn to(&self, Product(a, b): Product<A, B>) -> Product<B, A> {
+        Product(b, a)
+    }
+
+    fn from(&self, Product(b, a): Product<B, A>) -> Product<A, B> {
+        Product(a, b)
+    }
+}
+
+/// 交换和类型的前两个变体：`A + (B + R) ≅ B + (A + R)`
+pub struct SumSwap;
+
+impl<A, B, R> Iso<Sum<A, Sum<B, R>>, Sum<B, Sum<A, R>>> for SumSwap {
+    fn to(&self, a: Sum<A, Sum<B, R>>) -> Sum<B, Sum<A, R>> {
+        match a {
+            Sum::This(x) => Sum::Next(Sum::This(x)),
+            Sum::Next(Sum::This(x)) => Sum::This(x),
+            Sum::Next(Sum::Next(x)) => Sum::Next(Sum::Next(x)),
+        }
+    }
+
+    fn from(&self, b: Sum<B, Sum<A, R>>) -> Sum<A, Sum<B, R>> {
+        self.to(b)
+    }
+}
+
+/// 交换积类型的前两个字段：`A × (B × R) ≅ B × (A × R)`
+pub struct ProductSwap;
+
+impl<A, B, R> Iso<Product<A, Product<B, R>>, Product<B, Product<A, R>>> for ProductSwap {
+    fn to(
+        &self,
+        Product(a, Product(b, r)): Product<A, Product<B, R>>,
+    ) -> Product<B, Product<A, R>> {
+        Product(b, Product(a, r))
+    }
+
+    fn from(&self, b: Product<B, Product<A, R>>) -> Product<A, Product<B, R>> {
+        self.to(b)
+    }
+}
+
+// 结合律
+
+/// `(A + B) + C ≅ A + (B + C)`
+pub struct SumAssoc;
+
+impl<A, B, C> Iso<Sum<Sum<A, B>, C>, Sum<A, Sum<B, C>>> for SumAssoc {
+    fn to(&self, a: Sum<Sum<A, B>, C>) -> Sum<A, Sum<B, C>> {
+        match a {
+            Sum::This(Sum::This(x)) => Sum::This(x),
+            Sum::This(Sum::Next(x)) => Sum::Next(Sum::This(x)),
+            Sum::Next(x) => Sum::Next(Sum::Next(x)),
+        }
+    }
+
+    fn from(&self, b: Sum<A, Sum<B, C>>) -> Sum<Sum<A, B>, C> {
+        match b {
+            Sum::This(x) => Sum::This(Sum::This(x)),
+            Sum::Next(Sum::This(x)) => Sum::This(Sum::Next(x)),
+            Sum::Next(Sum::Next(x)) => Sum::Next(x),
+        }
+    }
+}
+
+/// `(A × B) × C ≅ A × (B × C)`
+pub struct ProductAssoc;
+
+impl<A, B, C> Iso<Product<Product<A, B>, C>, Product<A, Product<B, C>>> for ProductAssoc {
+    fn to(
+        &self,
+        Product(Product(a, b), c): Product<Product<A, B>, C>,
+    ) -> Product<A, Product<B, C>> {
+        Product(a, Product(b, c))
+    }
+
+    fn from(
+        &self,
+        Product(a, Product(b, c)): Product<A, Product<B, C>>,
+    ) -> Product<Product<A, B>, C> {
+        Product(Product(a, b), c)
+    }
+}
+
+// 分配律
+
+/// `A × (B + C) ≅ A × B + A × C`
+pub struct Distrib;
+
+impl<A, B, C> Iso<Product<A, Sum<B, C>>, Sum<Product<A, B>, Product<A, C>>> for Distrib {
+    fn to(&self, Product(a, x): Product<A, Sum<B, C>>) -> Sum<Product<A, B>, Product<A, C>> {
+        match x {
+            Sum::This(b) => Sum::This(Product(a, b)),
+            Sum::Next(c) => Sum::Next(Product(a, c)),
+        }
+    }
+
+    fn from(&self, b: Sum<Product<A, B>, Product<A, C>>) -> Product<A, Sum<B, C>> {
+        match b {
+            Sum::This(Product(a, b)) => Product(a, Sum::This(b)),
+            Sum::Next(Product(a, c)) => Product(a, Sum::Next(c)),
+        }
+    }
+}
+
+// 单位元和零元
+
+/// `A + 0 ≅ A`
+pub struct SumUnit;
+
+impl<A> Iso<Sum<A, Zero>, A> for SumUnit {
+    fn to(&self, a: Sum<A, Zero>) -> A {
+        match a {
+            Sum::This(x) => x,
+            Sum::Next(x) => match x {},
+        }
+    }
+
+    fn from(&self, b: A) -> Sum<A, Zero> {
+        Sum::This(b)
+    }
+}
+
+/// `A × 1 ≅ A`
+pub struct ProductUnit;
+
+impl<A> Iso<Product<A, One>, A> for ProductUnit {
+    fn to(&self, Product(a, One): Product<A, One>) -> A {
+        a
+    }
+
+    fn from(&self, b: A) -> Product<A, One> {
+        Product(b, One)
+    }
+}
+
+/// `A × 0 ≅ 0`
+pub struct ProductZero;
+
+impl<A> Iso<Product<A, Zero>, Zero> for ProductZero {
+    fn to(&self, Product(_, z): Product<A, Zero>) -> Zero {
+        z
+    }
+
+    fn from(&self, b: Zero) -> Product<A, Zero> {
+        match b {}
+    }
+}
@@ -0,0 +1,887 @@
+//! 不依赖 serde 的 JSON 编码与解码
+//!
+//! 实现 [`Json`] 者可与 [`JsonValue`] 互转，进而以 [`to_string`] 和 [`from_str`] 读写 JSON 文本。
+//! 可使用 [`GenericJson`] 自动实现。
+//!
+//! 与 JSON 的对应和 [`Schema::to_json_schema`](crate::schema::Schema::to_json_schema) 一致：
+//!
+//! - 具名字段的 struct 对应对象，匿名字段的对应数组，无字段的对应 `null`
+//! - enum 对应外部标签的对象，如 `{"Circle": {"r": 1.0}}`；无字段的变体对应字符串
+//! - 序列对应数组，`()` 对应 `null`，`char` 对应长为一的字符串，非有限的浮点数对应 `null`
+//!
+//! 若类型没有名字信息（[`Generic::META`]），则变体以序号为名。
+//! 解码的错误带有出错处的路径，如 `$.tags[1].Struct.type`。
+//!
+//! 不支持递归的类型，否则会无限递归。
+
+use crate::{
+    schema::write_json_str, visit_tuple, FieldsMeta, Generic, One, Product, Repr, Sum, VariantMeta,
+    Zero,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+/// JSON 值
+///
+/// 数保留其原文，以免损失整数的精度。
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// 对象，保留键的顺序
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// 值的种类，用于错误信息
+    fn kind(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "boolean",
+            JsonValue::Number(_) => "number",
+            JsonValue::String(_) => "string",
+            JsonValue::Array(_) => "array",
+            JsonValue::Object(_) => "object",
+        }
+    }
+
+    fn write(&self, out: &mut String) {
+        match self {
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Bool(x) => out.push_str(if *x { "true" } else { "false" }),
+            JsonValue::Number(x) => out.push_str(x),
+            JsonValue::String(x) => write_json_str(out, x),
+            JsonValue::Array(xs) => {
+                out.push('[');
+                for (i, x) in xs.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    x.write(out);
+                }
+                out.push(']');
+            }
+            JsonValue::Object(xs) => {
+                out.push('{');
+                for (i, (k, x)) in xs.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    write_json_str(out, k);
+                    out.push(':');
+                    x.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// 打印为紧凑的 JSON 文本
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        self.write(&mut out);
+        f.write_str(&out)
+    }
+}
+
+/// 解析 JSON 文本
+impl FromStr for JsonValue {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut p = Parser {
+            s: s.as_bytes(),
+            pos: 0,
+            depth: 0,
+        };
+        let v = p.value()?;
+        p.ws();
+        match p.pos == p.s.len() {
+            true => Ok(v),
+            false => Err(p.error("end of input")),
+        }
+    }
+}
+
+/// 嵌套的最大深度
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, expected: &'static str) -> Error {
+        Error::new(ErrorKind::Syntax {
+            offset: self.pos,
+            expected,
+        })
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn ws(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        self.ws();
+        let eaten = self.peek() == Some(c);
+        self.pos += eaten as usize;
+        eaten
+    }
+
+    fn expect(&mut self, c: u8, expected: &'static str) -> Result<(), Error> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(self.error(expected)),
+        }
+    }
+
+    fn keyword(&mut self, word: &str, v: JsonValue) -> Result<JsonValue, Error> {
+        match self.s[self.pos..].starts_with(word.as_bytes()) {
+            true => {
+                self.pos += word.len();
+                Ok(v)
+            }
+            false => Err(self.error("value")),
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue, Error> {
+        self.ws();
+        match self.peek() {
+            Some(b'n') => self.keyword("null", JsonValue::Null),
+            Some(b't') => self.keyword("true", JsonValue::Bool(true)),
+            Some(b'f') => self.keyword("false", JsonValue::Bool(false)),
+            Some(b'"') => self.string().map(JsonValue::String),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(b'[') => self.nested(|p| {
+                let mut xs = Vec::new();
+                if p.eat(b']') {
+                    return Ok(JsonValue::Array(xs));
+                }
+                loop {
+                    xs.push(p.value()?);
+                    if p.eat(b']') {
+                        return Ok(JsonValue::Array(xs));
+                    }
+                    p.expect(b',', "`,` or `]`")?;
+                }
+            }),
+            Some(b'{') => self.nested(|p| {
+                let mut xs = Vec::new();
+                if p.eat(b'}') {
+                    return Ok(JsonValue::Object(xs));
+                }
+                loop {
+                    p.ws();
+                    if p.peek() != Some(b'"') {
+                        return Err(p.error("string"));
+                    }
+                    let k = p.string()?;
+                    p.expect(b':', "`:`")?;
+                    xs.push((k, p.value()?));
+                    if p.eat(b'}') {
+                        return Ok(JsonValue::Object(xs));
+                    }
+                    p.expect(b',', "`,` or `}`")?;
+                }
+            }),
+            _ => Err(self.error("value")),
+        }
+    }
+
+    /// 解析数组或对象，跳过起始的括号
+    fn nested(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<JsonValue, Error>,
+    ) -> Result<JsonValue, Error> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("shallower nesting"));
+        }
+        self.pos += 1;
+        self.depth += 1;
+        let v = f(self)?;
+        self.depth -= 1;
+        Ok(v)
+    }
+
+    fn digits(&mut self) -> Result<(), Error> {
+        let start = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        match self.pos > start {
+            true => Ok(()),
+            false => Err(self.error("digit")),
+        }
+    }
+
+    fn number(&mut self) -> Result<JsonValue, Error> {
+        let start = self.pos;
+        self.pos += (self.peek() == Some(b'-')) as usize;
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            _ => self.digits()?,
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            self.digits()?;
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            self.digits()?;
+        }
+        // 只含 ASCII 字符
+        let s = core::str::from_utf8(&self.s[start..self.pos]).unwrap();
+        Ok(JsonValue::Number(s.into()))
+    }
+
+    fn hex4(&mut self) -> Result<u32, Error> {
+        let hex = self.s.get(self.pos..self.pos + 4).unwrap_or_default();
+        let x = core::str::from_utf8(hex)
+            .ok()
+            .filter(|h| h.len() == 4 && h.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|h| u32::from_str_radix(h, 16).ok())
+            .ok_or_else(|| self.error("four hex digits"))?;
+        self.pos += 4;
+        Ok(x)
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            while self
+                .peek()
+                .is_some_and(|c| c != b'"' && c != b'\\' && c >= b' ')
+            {
+                self.pos += 1;
+            }
+            // 输入是 `&str`，在 ASCII 字符处切分仍是合法的 UTF-8
+            out.push_str(core::str::from_utf8(&self.s[start..self.pos]).unwrap());
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => self.pos += 1,
+                _ => return Err(self.error("`\"`")),
+            }
+            let c = match self.peek() {
+                Some(b'"') => '"',
+                Some(b'\\') => '\\',
+                Some(b'/') => '/',
+                Some(b'b') => '\u{8}',
+                Some(b'f') => '\u{c}',
+                Some(b'n') => '\n',
+                Some(b'r') => '\r',
+                Some(b't') => '\t',
+                Some(b'u') => {
+                    self.pos += 1;
+                    let hi = self.hex4()?;
+                    let c = match hi {
+                        0xd800..=0xdbff => {
+                            if !self.s[self.pos..].starts_with(b"\\u") {
+                                return Err(self.error("low surrogate"));
+                            }
+                            self.pos += 2;
+                            let lo = self.hex4()?;
+                            if !(0xdc00..=0xdfff).contains(&lo) {
+                                return Err(self.error("low surrogate"));
+                            }
+                            0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00)
+                        }
+                        c => c,
+                    };
+                    out.push(char::from_u32(c).ok_or_else(|| self.error("unicode scalar"))?);
+                    continue;
+                }
+                _ => return Err(self.error("escape")),
+            };
+            self.pos += 1;
+            out.push(c);
+        }
+    }
+}
+
+/// 路径的一段
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// 有名字的变体或字段
+    Name(&'static str),
+    /// 没有名字的变体或字段，或序列的元素
+    Index(usize),
+}
+
+impl Segment {
+    fn new(name: Option<&'static str>, index: usize) -> Self {
+        match name {
+            Some(name) => Segment::Name(name),
+            None => Segment::Index(index),
+        }
+    }
+}
+
+/// 错误的种类
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// JSON 文本不合语法，`offset` 为出错处的字节位置
+    Syntax {
+        offset: usize,
+        expected: &'static str,
+    },
+    /// 值的种类不符
+    Expected {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// 数组的长度不符
+    Length { expected: usize, found: usize },
+    /// 缺少字段
+    MissingField(&'static str),
+    /// 多余的字段
+    UnknownField(String),
+    /// 没有此名字的变体
+    UnknownVariant(String),
+    /// 数超出类型的范围
+    OutOfRange,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Syntax { offset, expected } => {
+                write!(f, "expected {expected} at offset {offset}")
+            }
+            ErrorKind::Expected { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+            ErrorKind::Length { expected, found } => {
+                write!(f, "expected array of length {expected}, found {found}")
+            }
+            ErrorKind::MissingField(name) => write!(f, "missing field `{name}`"),
+            ErrorKind::UnknownField(name) => write!(f, "unknown field `{name}`"),
+            ErrorKind::UnknownVariant(name) => write!(f, "unknown variant `{name}`"),
+            ErrorKind::OutOfRange => f.write_str("number out of range"),
+        }
+    }
+}
+
+/// 编码或解码的错误，带有出错处的路径
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    /// 由内向外
+    path: Vec<Segment>,
+}
+
+impl Error {
+    /// 在根处的错误
+    pub fn new(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            path: Vec::new(),
+        }
+    }
+
+    /// 值的种类不符
+    pub fn expected(expected: &'static str, found: &JsonValue) -> Self {
+        Self::new(ErrorKind::Expected {
+            expected,
+            found: found.kind(),
+        })
+    }
+
+    /// 将路径置于 `segment` 之内
+    pub fn within(mut self, segment: Segment) -> Self {
+        self.path.push(segment);
+        self
+    }
+
+    /// 错误的种类
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// 出错处的路径，由外向内
+    pub fn path(&self) -> impl Iterator<Item = &Segment> {
+        self.path.iter().rev()
+    }
+}
+
+/// 打印为如 `missing field `r` at $.shapes[0].Circle`
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at $", self.kind)?;
+        for s in self.path() {
+            match s {
+                Segment::Name(name) => write!(f, ".{name}")?,
+                Segment::Index(i) => write!(f, "[{i}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// 可与 JSON 值互转者
+///
+/// 建议使用 [`GenericJson`] 自动实现。
+pub trait Json: Sized {
+    /// 转换成 JSON 值
+    fn to_json_value(&self) -> JsonValue;
+    /// 从 JSON 值转换
+    fn from_json_value(v: &JsonValue) -> Result<Self, Error>;
+}
+
+/// 编码为 JSON 文本
+pub fn to_string<T: Json>(x: &T) -> String {
+    x.to_json_value().to_string()
+}
+
+/// 从 JSON 文本解码
+pub fn from_str<T: Json>(s: &str) -> Result<T, Error> {
+    T::from_json_value(&s.parse()?)
+}
+
+/// 表示上的 [`Json`]，用于和类型
+///
+/// `variants` 是从当前变体开始的名字信息，`index` 是当前变体的序号。
+pub trait GJson: Repr + Sized {
+    /// 变体的数量
+    const COUNT: usize;
+    /// 转换成 JSON 值，`is_enum` 决定是否加上变体的标签
+    fn g_to_json(
+        this: &Self::Ref<'_>,
+        variants: Option<&[VariantMeta]>,
+        index: usize,
+        is_enum: bool,
+    ) -> JsonValue;
+    /// 从第 `target` 个变体的载荷转换，无字段的变体没有载荷
+    fn g_from_json(
+        target: usize,
+        variants: Option<&[VariantMeta]>,
+        index: usize,
+        payload: Option<&JsonValue>,
+    ) -> Result<Self, Error>;
+}
+
+/// 表示上的 [`Json`]，用于积类型
+///
+/// `variant` 是所在变体的名字信息，`index` 是字段的位置。
+pub trait GJsonFields: Repr + Sized {
+    /// 字段的数量
+    const LEN: usize;
+    fn g_to_json(this: &Self::Ref<'_>, out: &mut Vec<JsonValue>);
+    fn g_from_json(
+        variant: Option<&VariantMeta>,
+        index: usize,
+        fields: &[&JsonValue],
+    ) -> Result<Self, Error>;
+}
+
+fn variant_name(variants: Option<&[VariantMeta]>, index: usize) -> String {
+    variants
+        .and_then(<[_]>::first)
+        .map_or_else(|| index.to_string(), |v| v.name.into())
+}
+
+fn fields_kind(variant: Option<&VariantMeta>, len: usize) -> FieldsMeta {
+    match variant {
+        Some(v) => v.fields,
+        None if len == 0 => FieldsMeta::Unit,
+        None => FieldsMeta::Unnamed,
+    }
+}
+
+impl GJson for Zero {
+    const COUNT: usize = 0;
+
+    fn g_to_json(
+        this: &Self::Ref<'_>,
+        _variants: Option<&[VariantMeta]>,
+        _index: usize,
+        _is_enum: bool,
+    ) -> JsonValue {
+        match *this {}
+    }
+
+    fn g_from_json(
+        _target: usize,
+        _variants: Option<&[VariantMeta]>,
+        _index: usize,
+        _payload: Option<&JsonValue>,
+    ) -> Result<Self, Error> {
+        unreachable!("variant index out of range")
+    }
+}
+
+impl<T: GJsonFields, R: GJson> GJson for Sum<T, R> {
+    const COUNT: usize = 1 + R::COUNT;
+
+    fn g_to_json(
+        this: &Self::Ref<'_>,
+        variants: Option<&[VariantMeta]>,
+        index: usize,
+        is_enum: bool,
+    ) -> JsonValue {
+        let x = match this {
+            Sum::This(x) => x,
+            Sum::Next(x) => {
+                return R::g_to_json(x, variants.and_then(|v| v.get(1..)), index + 1, is_enum)
+            }
+        };
+        let name = variant_name(variants, index);
+        if is_enum && T::LEN == 0 {
+            return JsonValue::String(name);
+        }
+        let mut fields = Vec::new();
+        T::g_to_json(x, &mut fields);
+        let variant = variants.and_then(<[_]>::first);
+        let payload = match fields_kind(variant, T::LEN) {
+            FieldsMeta::Unit => JsonValue::Null,
+            FieldsMeta::Unnamed => JsonValue::Array(fields),
+            FieldsMeta::Named(names) => {
+                JsonValue::Object(names.iter().map(|&n| n.into()).zip(fields).collect())
+            }
+        };
+        match is_enum {
+            true => JsonValue::Object(vec![(name, payload)]),
+            false => payload,
+        }
+    }
+
+    fn g_from_json(
+        target: usize,
+        variants: Option<&[VariantMeta]>,
+        index: usize,
+        payload: Option<&JsonValue>,
+    ) -> Result<Self, Error> {
+        if target != index {
+            let variants = variants.and_then(|v| v.get(1..));
+            return R::g_from_json(target, variants, index + 1, payload).map(Sum::Next);
+        }
+        let variant = variants.and_then(<[_]>::first);
+        // 只有名字时，须为无字段的变体，否则应是带标签的对象
+        let Some(payload) = payload else {
+            return match T::LEN {
+                0 => T::g_from_json(variant, 0, &[]).map(Sum::This),
+                _ => Err(Error::new(ErrorKind::Expected {
+                    expected: "object with one key",
+                    found: "string",
+                })),
+            };
+        };
+        let fields = match (fields_kind(variant, T::LEN), payload) {
+            (FieldsMeta::Unit, JsonValue::Null) => Vec::new(),
+            (FieldsMeta::Unit, v) => return Err(Error::expected("null", v)),
+            (FieldsMeta::Unnamed, JsonValue::Array(xs)) if xs.len() == T::LEN => {
+                xs.iter().collect()
+            }
+            (FieldsMeta::Unnamed, JsonValue::Array(xs)) => {
+                return Err(Error::new(ErrorKind::Length {
+                    expected: T::LEN,
+                    found: xs.len(),
+                }))
+            }
+            (FieldsMeta::Unnamed, v) => return Err(Error::expected("array", v)),
+            (FieldsMeta::Named(names), JsonValue::Object(xs)) => {
+                if let Some((k, _)) = xs.iter().find(|(k, _)| !names.contains(&&**k)) {
+                    return Err(Error::new(ErrorKind::UnknownField(k.clone())));
+                }
+                let field = |&n: &&'static str| {
+                    let x = xs.iter().find(|(k, _)| k == n).map(|(_, x)| x);
+                    x.ok_or(Error::new(ErrorKind::MissingField(n)))
+                };
+                names.iter().map(field).collect::<Result<_, _>>()?
+            }
+            (FieldsMeta::Named(_), v) => return Err(Error::expected("object", v)),
+        };
+        T::g_from_json(variant, 0, &fields).map(Sum::This)
+    }
+}
+
+impl GJsonFields for One {
+    const LEN: usize = 0;
+
+    fn g_to_json(_this: &Self::Ref<'_>, _out: &mut Vec<JsonValue>) {}
+
+    fn g_from_json(
+        _variant: Option<&VariantMeta>,
+        _index: usize,
+        _fields: &[&JsonValue],
+    ) -> Result<Self, Error> {
+        Ok(One)
+    }
+}
+
+impl<T: Json, R: GJsonFields> GJsonFields for Product<T, R> {
+    const LEN: usize = 1 + R::LEN;
+
+    fn g_to_json(this: &Self::Ref<'_>, out: &mut Vec<JsonValue>) {
+        out.push(this.0.to_json_value());
+        R::g_to_json(&this.1, out);
+    }
+
+    fn g_from_json(
+        variant: Option<&VariantMeta>,
+        index: usize,
+        fields: &[&JsonValue],
+    ) -> Result<Self, Error> {
+        let name = variant.and_then(|v| v.fields.name(index));
+        let x =
+            T::from_json_value(fields[index]).map_err(|e| e.within(Segment::new(name, index)))?;
+        Ok(Product(x, R::g_from_json(variant, index + 1, fields)?))
+    }
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericJson`]，将会自动实现 [`Json`]。
+///
+/// 有名字信息者，按其是否为 enum 决定是否加上变体的标签；否则，仅有一个变体者视为 struct。
+///
+/// ```
+/// # use algtype::{Generic, json::{self, GenericJson}};
+/// #[derive(Generic, Debug, PartialEq)]
+/// enum Shape {
+///     Circle { r: f64 },
+///     Rect(u32, u32),
+///     Empty,
+/// }
+///
+/// impl GenericJson for Shape {}
+///
+/// let shapes = vec![Shape::Circle { r: 1.5 }, Shape::Rect(2, 3), Shape::Empty];
+/// let s = r#"[{"Circle":{"r":1.5}},{"Rect":[2,3]},"Empty"]"#;
+/// assert_eq!(json::to_string(&shapes), s);
+/// assert_eq!(json::from_str::<Vec<Shape>>(s).unwrap(), shapes);
+///
+/// let e = json::from_str::<Vec<Shape>>(r#"[{"Rect":[2,-3]}]"#).unwrap_err();
+/// assert_eq!(e.to_string(), "number out of range at $[0].Rect[1]");
+/// ```
+pub trait GenericJson: Generic
+where
+    Self::Repr: GJson,
+{
+}
+
+impl<T: GenericJson> Json for T
+where
+    T::Repr: GJson,
+{
+    fn to_json_value(&self) -> JsonValue {
+        let variants = T::META.map(|m| m.variants);
+        T::Repr::g_to_json(&self.as_repr(), variants, 0, !is_struct::<T>())
+    }
+
+    fn from_json_value(v: &JsonValue) -> Result<Self, Error> {
+        let variants = T::META.map(|m| m.variants);
+        if is_struct::<T>() {
+            return T::Repr::g_from_json(0, variants, 0, Some(v)).map(T::from_repr);
+        }
+        let (name, payload) = match v {
+            JsonValue::String(name) => (name, None),
+            JsonValue::Object(xs) if xs.len() == 1 => (&xs[0].0, Some(&xs[0].1)),
+            v => return Err(Error::expected("string or object with one key", v)),
+        };
+        let index = (0..T::Repr::COUNT)
+            .find(|&i| match variants {
+                Some(vs) => vs[i].name == name,
+                None => i.to_string() == *name,
+            })
+            .ok_or_else(|| Error::new(ErrorKind::UnknownVariant(name.clone())))?;
+        let x = T::Repr::g_from_json(index, variants, 0, payload).map(T::from_repr);
+        match payload {
+            Some(_) => {
+                let segment = Segment::new(variants.map(|vs| vs[index].name), index);
+                x.map_err(|e| e.within(segment))
+            }
+            // 错误在于值本身而非载荷，路径停在 enum 处
+            None => x,
+        }
+    }
+}
+
+fn is_struct<T: Generic>() -> bool
+where
+    T::Repr: GJson,
+{
+    T::META.map_or(T::Repr::COUNT == 1, |m| !m.is_enum)
+}
+
+// ADT
+
+impl<T: Json> GenericJson for Option<T> {}
+
+impl<T: Json, E: Json> GenericJson for Result<T, E> {}
+
+// 数组
+
+impl<T, const N: usize> GenericJson for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GJson,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    () => {};
+    ($($tys:ident)*) => {
+        impl <$($tys: Json),*> GenericJson for ($($tys,)*) {}
+    };
+}
+
+visit_tuple!(impl_tuple);
+
+// 基本类型
+
+impl Json for () {
+    fn to_json_value(&self) -> JsonValue {
+        JsonValue::Null
+    }
+
+    fn from_json_value(v: &JsonValue) -> Result<Self, Error> {
+        match v {
+            JsonValue::Null => Ok(()),
+            v => Err(Error::expected("null", v)),
+        }
+    }
+}
+
+impl Json for bool {
+    fn to_json_value(&self) -> JsonValue {
+        JsonValue::Bool(*self)
+    }
+
+    fn from_json_value(v: &JsonValue) -> Result<Self, Error> {
+        match v {
+            JsonValue::Bool(x) => Ok(*x),
+            v => Err(Error::expected("boolean", v)),
+        }
+    }
+}
+
+macro_rules! impl_int {
+    ($($ty:ty)*) => {$(
+        impl Json for $ty {
+            fn to_json_value(&self) -> JsonValue {
+                JsonValue::Number(self.to_string())
+            }
+
+            fn from_json_value(v: &JsonValue) -> Result<Self, Error> {
+                let JsonValue::Number(x) = v else {
+                    return Err(Error::expected("integer", v));
+                };
+                if x.contains(['.', 'e', 'E']) {
+                    return Err(Error::expected("integer", v));
+                }
+                x.parse().map_err(|_| Error::new(ErrorKind::OutOfRange))
+            }
+        }
+    )*};
+}
+
+impl_int!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+macro_rules! impl_float {
+    ($($ty:ty)*) => {$(
+        impl Json for $ty {
+            fn to_json_value(&self) -> JsonValue {
+                match self.is_finite() {
+                    // `Debug` 在数很大或很小时使用指数形式
+                    true => JsonValue::Number(format!("{self:?}")),
+                    false => JsonValue::Null,
+                }
+            }
+
+            fn from_json_value(v: &JsonValue) -> Result<Self, Error> {
+                let JsonValue::Number(x) = v else {
+                    return Err(Error::expected("number", v));
+                };
+                x.parse().map_err(|_| Error::new(ErrorKind::OutOfRange))
+            }
+        }
+    )*};
+}
+
+impl_float!(f32 f64);
+
+impl Json for char {
+    fn to_json_value(&self) -> JsonValue {
+        JsonValue::String((*self).into())
+    }
+
+    fn from_json_value(v: &JsonValue) -> Result<Self, Error> {
+        if let JsonValue::String(s) = v {
+            let mut chars = s.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                return Ok(c);
+            }
+        }
+        Err(Error::expected("string of one character", v))
+    }
+}
+
+impl Json for String {
+    fn to_json_value(&self) -> JsonValue {
+        JsonValue::String(self.clone())
+    }
+
+    fn from_json_value(v: &JsonValue) -> Result<Self, Error> {
+        match v {
+            JsonValue::String(x) => Ok(x.clone()),
+            v => Err(Error::expected("string", v)),
+        }
+    }
+}
+
+impl<T: Json> Json for Vec<T> {
+    fn to_json_value(&self) -> JsonValue {
+        JsonValue::Array(self.iter().map(T::to_json_value).collect())
+    }
+
+    fn from_json_value(v: &JsonValue) -> Result<Self, Error> {
+        let JsonValue::Array(xs) = v else {
+            return Err(Error::expected("array", v));
+        };
+        let elem = |(i, x)| T::from_json_value(x).map_err(|e| e.within(Segment::Index(i)));
+        xs.iter().enumerate().map(elem).collect()
+    }
+}
+
+/// 原样保留，用于结构不定的字段
+impl Json for JsonValue {
+    fn to_json_value(&self) -> JsonValue {
+        self.clone()
+    }
+
+    fn from_json_value(v: &JsonValue) -> Result<Self, Error> {
+        Ok(v.clone())
+    }
+}
@@ -3,13 +3,51 @@
 //!
 //! [`utils`] 模块提供了有用（其实没啥用）的方法以操作实现 [`Generic`] 的类型。
 //!
+//! 需要堆分配的功能，如 `dynamic` 模块，需启用 `alloc` 特性。
+//! 启用 `testing` 特性后，`testing` 模块提供检验 [`Generic`] 的实现的函数。
+//!
 //! README 有额外信息，[`Generic`] 的文档有详细说明。
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "testing")]
+extern crate std;
+
+pub mod approx_eq;
+pub mod builder;
+pub mod coproduct;
+#[cfg(feature = "alloc")]
+pub mod csv;
+pub mod data;
+pub mod dispatch;
+#[cfg(feature = "alloc")]
+pub mod dynamic;
+pub mod flatten;
 mod generic;
+pub mod heap_size;
+pub mod hlist;
 mod impls;
+pub mod index;
+pub mod iso;
+#[cfg(feature = "alloc")]
+pub mod json;
+pub mod pointwise;
+pub mod prism;
+#[cfg(feature = "alloc")]
+pub mod schema;
+pub mod select;
+pub mod shape_hash;
+pub mod stable_hash;
+#[cfg(feature = "alloc")]
+pub mod tagged;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod utils;
+pub mod variant_name;
 
-pub use algtype_derive::Generic;
+pub use algtype_derive::{
+    Generic, GenericAdd, GenericDisplay, GenericFromStr, GenericMul, GenericNeg, GenericSub,
+};
 pub use generic::*;
@@ -0,0 +1,190 @@
+//! 逐字段的运算
+//!
+//! [`ZipFields`] 以多态函数（[`Poly`]）逐字段合并同一类型的两个值，[`MapFields`] 逐字段变换。
+//! 二者均用于仅有一个变体的类型，如 struct 和元组。
+//!
+//! 派生 [`GenericAdd`](crate::GenericAdd)、[`GenericSub`](crate::GenericSub)、
+//! [`GenericMul`](crate::GenericMul) 和 [`GenericNeg`](crate::GenericNeg)
+//! 以逐字段地实现 `core::ops` 中的运算：
+//!
+//! - `GenericAdd` 实现 `Add` 与 `AddAssign`
+//! - `GenericSub` 实现 `Sub` 与 `SubAssign`
+//! - `GenericMul` 实现 `Mul` 与 `MulAssign`
+//! - `GenericNeg` 实现 `Neg`，与 `GenericSub` 分开，因为无符号数能减而不能取负
+//!
+//! 孤儿规则不允许以标记 trait 自动实现这些运算，因此使用派生。
+//! 与标量的乘法见 [`scale`]，它不能与逐字段的 `Mul` 同时实现。
+//!
+//! ```
+//! # use algtype::{Generic, GenericAdd, GenericMul, GenericNeg, GenericSub, pointwise::scale};
+//! #[derive(Generic, GenericAdd, GenericSub, GenericMul, GenericNeg, Clone, Copy, Debug, PartialEq)]
+//! struct Rgb<T> {
+//!     r: T,
+//!     g: T,
+//!     b: T,
+//! }
+//!
+//! let a = Rgb { r: 1.0, g: 2.0, b: 3.0 };
+//! let b = Rgb { r: 0.5, g: 0.5, b: 0.5 };
+//! assert_eq!(a + b, Rgb { r: 1.5, g: 2.5, b: 3.5 });
+//! assert_eq!(-(a - b), Rgb { r: -0.5, g: -1.5, b: -2.5 });
+//! assert_eq!(a * b, Rgb { r: 0.5, g: 1.0, b: 1.5 });
+//! assert_eq!(scale(a, 2.0), Rgb { r: 2.0, g: 4.0, b: 6.0 });
+//!
+//! let mut c = a;
+//! c += b;
+//! assert_eq!(c, a + b);
+//! ```
+
+use crate::{
+    flatten::Single,
+    hlist::{Map, Poly, ZipWith},
+    Generic, One, Product, Repr,
+};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+type FieldsOf<T> = <<T as Generic>::Repr as Single>::Product;
+
+/// 与 `R` 逐个以多态函数 `F` 原地合并，`F` 的参数是此处元素的可变引用和 `R` 的元素
+pub trait ZipWithMut<R, F>: Repr {
+    /// 依次调用
+    fn zip_with_mut(this: Self::Mut<'_>, r: R, f: &mut F);
+}
+
+impl<F> ZipWithMut<One, F> for One {
+    fn zip_with_mut(_this: One, _r: One, _f: &mut F) {}
+}
+
+impl<X, R: ZipWithMut<S, F>, Y, S, F> ZipWithMut<Product<Y, S>, F> for Product<X, R>
+where
+    F: for<'a> Poly<(&'a mut X, Y), Output = ()>,
+{
+    fn zip_with_mut(this: Self::Mut<'_>, r: Product<Y, S>, f: &mut F) {
+        f.call((this.0, r.0));
+        R::zip_with_mut(this.1, r.1, f);
+    }
+}
+
+/// 逐字段合并
+///
+/// 为仅有一个变体，且各字段能以 `F` 合并的类型自动实现。
+pub trait ZipFields<F>: Generic + Sized {
+    /// 逐字段以 `f` 合并 `self` 和 `r`
+    fn zip_fields(self, r: Self, f: F) -> Self;
+}
+
+impl<T: Generic, F> ZipFields<F> for T
+where
+    T::Repr: Single,
+    FieldsOf<T>: ZipWith<FieldsOf<T>, F, Output = FieldsOf<T>>,
+{
+    fn zip_fields(self, r: Self, mut f: F) -> Self {
+        let (x, y) = (
+            Single::unwrap(self.into_repr()),
+            Single::unwrap(r.into_repr()),
+        );
+        T::from_repr(Single::wrap(x.zip_by(y, &mut f)))
+    }
+}
+
+/// 逐字段原地合并
+///
+/// 为仅有一个变体，且各字段能以 `F` 原地合并的类型自动实现。
+pub trait ZipFieldsMut<F>: Generic + Sized {
+    /// 逐字段以 `f` 把 `r` 合并到 `self`
+    fn zip_fields_mut(&mut self, r: Self, f: F);
+}
+
+impl<T: Generic, F> ZipFieldsMut<F> for T
+where
+    T::Repr: Single,
+    FieldsOf<T>: ZipWithMut<FieldsOf<T>, F>,
+{
+    fn zip_fields_mut(&mut self, r: Self, mut f: F) {
+        let y = Single::unwrap(r.into_repr());
+        let x = T::Repr::unwrap_mut(self.as_mut_repr());
+        FieldsOf::<T>::zip_with_mut(x, y, &mut f);
+    }
+}
+
+/// 逐字段变换
+///
+/// 为仅有一个变体，且各字段能以 `F` 变换为同一类型的类型自动实现。
+pub trait MapFields<F>: Generic + Sized {
+    /// 逐字段以 `f` 变换
+    fn map_fields(self, f: F) -> Self;
+}
+
+impl<T: Generic, F> MapFields<F> for T
+where
+    T::Repr: Single,
+    FieldsOf<T>: Map<F, Output = FieldsOf<T>>,
+{
+    fn map_fields(self, mut f: F) -> Self {
+        let x = Single::unwrap(self.into_repr());
+        T::from_repr(Single::wrap(x.map_with(&mut f)))
+    }
+}
+
+/// 逐字段乘以标量
+pub fn scale<T: MapFields<Scale<S>>, S>(x: T, s: S) -> T {
+    x.map_fields(Scale(s))
+}
+
+macro_rules! binary {
+    ($($name:ident: $trait:ident::$method:ident, $assign:ident: $trait_assign:ident::$method_assign:ident;)*) => {$(
+        #[doc = concat!("以 `", stringify!($trait), "` 合并一对元素")]
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $name;
+
+        impl<X: $trait<Output = X>> Poly<(X, X)> for $name {
+            type Output = X;
+
+            fn call(&mut self, (x, y): (X, X)) -> X {
+                x.$method(y)
+            }
+        }
+
+        #[doc = concat!("以 `", stringify!($trait_assign), "` 原地合并一对元素")]
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $assign;
+
+        impl<X: $trait_assign> Poly<(&mut X, X)> for $assign {
+            type Output = ();
+
+            fn call(&mut self, (x, y): (&mut X, X)) {
+                x.$method_assign(y);
+            }
+        }
+    )*};
+}
+
+binary!(
+    AddFn: Add::add, AddAssignFn: AddAssign::add_assign;
+    SubFn: Sub::sub, SubAssignFn: SubAssign::sub_assign;
+    MulFn: Mul::mul, MulAssignFn: MulAssign::mul_assign;
+);
+
+/// 以 `Neg` 变换元素
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NegFn;
+
+impl<X: Neg<Output = X>> Poly<X> for NegFn {
+    type Output = X;
+
+    fn call(&mut self, x: X) -> X {
+        -x
+    }
+}
+
+/// 乘以标量
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Scale<S>(pub S);
+
+impl<X: Mul<S, Output = X>, S: Copy> Poly<X> for Scale<S> {
+    type Output = X;
+
+    fn call(&mut self, x: X) -> X {
+        x * self.0
+    }
+}
@@ -0,0 +1,168 @@
+//! 按位置访问变体
+//!
+//! [`Prism`] 能按变体的位置（见 [`index`](crate::index)）判断、取出和构造变体，
+//! 变体以其表示，即字段的积（[`Product`](crate::Product)）的形式出入。
+//! 与 [`select`](crate::select) 组合，能访问嵌套的载荷而无需手写 `match`。
+//!
+//! ```
+//! # use algtype::{Generic, prism::Prism, index::*, Product, One};
+//! #[derive(Generic, Debug, PartialEq)]
+//! enum Shape {
+//!     Circle { r: f64 },
+//!     Rect { w: u32, h: u32 },
+//! }
+//!
+//! let s = Shape::Rect { w: 2, h: 3 };
+//! assert!(s.is_variant::<I1>());
+//! assert!(s.as_variant::<I0>().is_none());
+//!
+//! // 取出 `Rect` 的第二个字段
+//! let h = s.as_variant::<I1>().map(|Product(_, Product(h, One))| *h);
+//! assert_eq!(h, Some(3));
+//!
+//! let c = Shape::from_variant::<I0>(Product(1.0, One));
+//! assert_eq!(c.into_variant::<I0>(), Ok(Product(1.0, One)));
+//! ```
+
+use crate::{
+    index::{Here, There},
+    Generic, Repr, Sum,
+};
+
+/// 和类型中位置为 `N` 的变体
+pub trait VariantAt<N>: Repr + Sized {
+    /// 变体的表示
+    type Variant: Repr;
+    /// 构造此变体
+    fn make(v: Self::Variant) -> Self;
+    /// 若是此变体，则取出，否则原样返回
+    fn take(this: Self) -> Result<Self::Variant, Self>;
+    /// 若是此变体，则取出其引用形式
+    fn take_ref<'a>(this: Self::Ref<'a>) -> Option<<Self::Variant as Repr>::Ref<'a>>
+    where
+        Self: 'a;
+    /// 若是此变体，则取出其可变引用形式
+    fn take_mut<'a>(this: Self::Mut<'a>) -> Option<<Self::Variant as Repr>::Mut<'a>>
+    where
+        Self: 'a;
+}
+
+impl<T: Repr, R: Repr> VariantAt<Here> for Sum<T, R> {
+    type Variant = T;
+
+    fn make(v: T) -> Self {
+        Sum::This(v)
+    }
+
+    fn take(this: Self) -> Result<T, Self> {
+        match this {
+            Sum::This(x) => Ok(x),
+            x => Err(x),
+        }
+    }
+
+    fn take_ref<'a>(this: Self::Ref<'a>) -> Option<T::Ref<'a>>
+    where
+        Self: 'a,
+    {
+        match this {
+            Sum::This(x) => Some(x),
+            Sum::Next(_) => None,
+        }
+    }
+
+    fn take_mut<'a>(this: Self::Mut<'a>) -> Option<T::Mut<'a>>
+    where
+        Self: 'a,
+    {
+        match this {
+            Sum::This(x) => Some(x),
+            Sum::Next(_) => None,
+        }
+    }
+}
+
+impl<T: Repr, R: VariantAt<N>, N> VariantAt<There<N>> for Sum<T, R> {
+    type Variant = R::Variant;
+
+    fn make(v: R::Variant) -> Self {
+        Sum::Next(R::make(v))
+    }
+
+    fn take(this: Self) -> Result<R::Variant, Self> {
+        match this {
+            Sum::This(x) => Err(Sum::This(x)),
+            Sum::Next(x) => R::take(x).map_err(Sum::Next),
+        }
+    }
+
+    fn take_ref<'a>(this: Self::Ref<'a>) -> Option<<R::Variant as Repr>::Ref<'a>>
+    where
+        Self: 'a,
+    {
+        match this {
+            Sum::This(_) => None,
+            Sum::Next(x) => R::take_ref(x),
+        }
+    }
+
+    fn take_mut<'a>(this: Self::Mut<'a>) -> Option<<R::Variant as Repr>::Mut<'a>>
+    where
+        Self: 'a,
+    {
+        match this {
+            Sum::This(_) => None,
+            Sum::Next(x) => R::take_mut(x),
+        }
+    }
+}
+
+/// `T` 的位置为 `N` 的变体的表示
+pub type VariantOf<T, N> = <<T as Generic>::Repr as VariantAt<N>>::Variant;
+
+/// 按位置判断、取出和构造变体
+///
+/// 为所有实现 [`Generic`] 的类型自动实现。位置超出变体的数量时会编译错误。
+pub trait Prism: Generic + Sized {
+    /// 是否为位置为 `N` 的变体
+    fn is_variant<N>(&self) -> bool
+    where
+        Self::Repr: VariantAt<N>,
+    {
+        self.as_variant::<N>().is_some()
+    }
+
+    /// 若是位置为 `N` 的变体，则取出其引用形式
+    fn as_variant<N>(&self) -> Option<<VariantOf<Self, N> as Repr>::Ref<'_>>
+    where
+        Self::Repr: VariantAt<N>,
+    {
+        Self::Repr::take_ref(self.as_repr())
+    }
+
+    /// 若是位置为 `N` 的变体，则取出其可变引用形式
+    fn as_variant_mut<N>(&mut self) -> Option<<VariantOf<Self, N> as Repr>::Mut<'_>>
+    where
+        Self::Repr: VariantAt<N>,
+    {
+        Self::Repr::take_mut(self.as_mut_repr())
+    }
+
+    /// 若是位置为 `N` 的变体，则取出，否则原样返回
+    fn into_variant<N>(self) -> Result<VariantOf<Self, N>, Self>
+    where
+        Self::Repr: VariantAt<N>,
+    {
+        Self::Repr::take(self.into_repr()).map_err(Self::from_repr)
+    }
+
+    /// 构造位置为 `N` 的变体
+    fn from_variant<N>(v: VariantOf<Self, N>) -> Self
+    where
+        Self::Repr: VariantAt<N>,
+    {
+        Self::from_repr(Self::Repr::make(v))
+    }
+}
+
+impl<T: Generic> Prism for T {}
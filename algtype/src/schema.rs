@@ -0,0 +1,644 @@
+//! 类型的结构描述
+//!
+//! [`Schema`] 描述了类型的结构：和、积以及有名字的基本类型，可用于别的语言生成代码。
+//! 它可以打印为 JSON Schema（[`Schema::to_json_schema`]），或以 [`Display`] 打印为简单的 IDL。
+//!
+//! 实现 [`Describe`] 者可以获取其结构描述，可使用 [`GenericDescribe`] 自动实现。
+//! 若类型有名字信息（[`Generic::META`]），则描述中包含名字。
+//!
+//! 递归的类型中，类型在其自身内部的出现描述为 [`Schema::Ref`]。
+
+use crate::{visit_tuple, FieldsMeta, Generic, One, Product, Repr, Sum, VariantMeta, Zero};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::fmt::{self, Display, Write};
+
+/// 类型的结构描述
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Schema {
+    /// 基本类型
+    ///
+    /// 名字是 `unit`、`bool`、`char`、`string`、`f32`、`f64`，或 `i8`、`u64` 之类的整数。
+    Leaf(&'static str),
+    /// 序列
+    Seq(Box<Schema>),
+    /// 代数数据类型
+    Adt(AdtSchema),
+    /// 正在描述的代数数据类型，用于递归的类型
+    Ref {
+        /// 类型的标识，见 [`AdtSchema::id`]
+        id: &'static str,
+        /// 类型的名字
+        name: Option<&'static str>,
+    },
+}
+
+/// 代数数据类型的结构描述，即积的和
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AdtSchema {
+    /// 类型的标识，即 [`type_name`](core::any::type_name)，用于区分同名的类型
+    pub id: &'static str,
+    /// 类型的名字
+    pub name: Option<&'static str>,
+    /// 是否为 enum
+    ///
+    /// 没有名字信息者，仅有一个变体者视为 struct。
+    pub is_enum: bool,
+    /// 各变体
+    pub variants: Vec<VariantSchema>,
+}
+
+/// 变体的结构描述
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VariantSchema {
+    /// 变体的名字
+    pub name: Option<&'static str>,
+    /// 字段的名字信息
+    pub kind: FieldsMeta,
+    /// 各字段
+    pub fields: Vec<FieldSchema>,
+}
+
+/// 字段的结构描述
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FieldSchema {
+    /// 字段的名字
+    pub name: Option<&'static str>,
+    /// 字段的类型
+    pub schema: Schema,
+}
+
+/// 可描述其结构者
+///
+/// 建议使用 [`GenericDescribe`] 自动实现。
+pub trait Describe {
+    /// 类型的结构描述，`stack` 为正在描述的类型的标识
+    fn describe(stack: &mut Vec<&'static str>) -> Schema;
+
+    /// 类型的结构描述
+    fn schema() -> Schema {
+        Self::describe(&mut Vec::new())
+    }
+}
+
+/// 表示上的 [`Describe`]，用于和类型
+///
+/// `variants` 是从当前变体开始的名字信息。
+pub trait GDescribe: Repr {
+    fn g_variants(
+        variants: Option<&[VariantMeta]>,
+        stack: &mut Vec<&'static str>,
+        out: &mut Vec<VariantSchema>,
+    );
+}
+
+/// 表示上的 [`Describe`]，用于积类型
+pub trait GDescribeFields: Repr {
+    fn g_fields(
+        variant: Option<&VariantMeta>,
+        index: usize,
+        stack: &mut Vec<&'static str>,
+        out: &mut Vec<FieldSchema>,
+    );
+}
+
+impl GDescribe for Zero {
+    fn g_variants(
+        _variants: Option<&[VariantMeta]>,
+        _stack: &mut Vec<&'static str>,
+        _out: &mut Vec<VariantSchema>,
+    ) {
+    }
+}
+
+impl<T: GDescribeFields, R: GDescribe> GDescribe for Sum<T, R> {
+    fn g_variants(
+        variants: Option<&[VariantMeta]>,
+        stack: &mut Vec<&'static str>,
+        out: &mut Vec<VariantSchema>,
+    ) {
+        let variant = variants.and_then(<[_]>::first);
+        let mut fields = Vec::new();
+        T::g_fields(variant, 0, stack, &mut fields);
+        let kind = match variant {
+            Some(v) => v.fields,
+            None if fields.is_empty() => FieldsMeta::Unit,
+            None => FieldsMeta::Unnamed,
+        };
+        out.push(VariantSchema {
+            name: variant.map(|v| v.name),
+            kind,
+            fields,
+        });
+        R::g_variants(variants.and_then(|v| v.get(1..)), stack, out);
+    }
+}
+
+impl GDescribeFields for One {
+    fn g_fields(
+        _variant: Option<&VariantMeta>,
+        _index: usize,
+        _stack: &mut Vec<&'static str>,
+        _out: &mut Vec<FieldSchema>,
+    ) {
+    }
+}
+
+impl<T: Describe, R: GDescribeFields> GDescribeFields for Product<T, R> {
+    fn g_fields(
+        variant: Option<&VariantMeta>,
+        index: usize,
+        stack: &mut Vec<&'static str>,
+        out: &mut Vec<FieldSchema>,
+    ) {
+        out.push(FieldSchema {
+            name: variant.and_then(|v| v.fields.name(index)),
+            schema: T::describe(stack),
+        });
+        R::g_fields(variant, index + 1, stack, out);
+    }
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericDescribe`]，将会自动实现 [`Describe`]。
+///
+/// ```
+/// # use algtype::{Generic, schema::{Describe, GenericDescribe}};
+/// #[derive(Generic)]
+/// enum Shape {
+///     Circle { r: f64 },
+///     Rect(u32, u32),
+///     Empty,
+/// }
+///
+/// impl GenericDescribe for Shape {}
+///
+/// let idl = "\
+/// enum Shape {
+///     Circle { r: f64 },
+///     Rect(u32, u32),
+///     Empty,
+/// }";
+/// assert_eq!(Shape::schema().to_string(), idl);
+/// ```
+///
+/// 递归的类型无法如此实现，因为约束会无限展开。此时可用 [`generic_describe`] 手动实现：
+///
+/// ```
+/// # use algtype::{Generic, schema::{self, Describe, Schema}};
+/// #[derive(Generic)]
+/// enum Tree {
+///     Leaf(i32),
+///     Node(Vec<Tree>),
+/// }
+///
+/// impl Describe for Tree {
+///     fn describe(stack: &mut Vec<&'static str>) -> Schema {
+///         schema::generic_describe::<Self>(stack)
+///     }
+/// }
+///
+/// assert_eq!(Tree::schema().to_string(), "enum Tree {\n    Leaf(i32),\n    Node([Tree]),\n}");
+/// ```
+pub trait GenericDescribe: Generic
+where
+    Self::Repr: GDescribe,
+{
+}
+
+impl<T: GenericDescribe> Describe for T
+where
+    T::Repr: GDescribe,
+{
+    fn describe(stack: &mut Vec<&'static str>) -> Schema {
+        generic_describe::<T>(stack)
+    }
+}
+
+/// 基于 [`Generic`] 的 [`Describe::describe`]
+pub fn generic_describe<T: Generic>(stack: &mut Vec<&'static str>) -> Schema
+where
+    T::Repr: GDescribe,
+{
+    let (id, name) = (core::any::type_name::<T>(), T::META.map(|m| m.name));
+    if stack.contains(&id) {
+        return Schema::Ref { id, name };
+    }
+    stack.push(id);
+    let mut variants = Vec::new();
+    T::Repr::g_variants(T::META.map(|m| m.variants), stack, &mut variants);
+    stack.pop();
+    Schema::Adt(AdtSchema {
+        id,
+        name,
+        is_enum: T::META.map_or(variants.len() != 1, |m| m.is_enum),
+        variants,
+    })
+}
+
+// JSON Schema
+
+impl Schema {
+    /// 打印为 JSON Schema
+    ///
+    /// 与 JSON 的对应如下：
+    ///
+    /// - 具名字段的 struct 对应对象，匿名字段的对应数组，无字段的对应 `null`
+    /// - enum 对应外部标签的对象，如 `{"Circle": {"r": 1.0}}`；无字段的变体对应字符串
+    /// - 序列对应数组，`unit` 对应 `null`，`char` 对应长为一的字符串，整数带有其范围
+    ///
+    /// 有名字的类型，以及递归的类型，只在 `$defs` 中定义一次，以名字为键（同名者加上序号），
+    /// 其他地方以 `$ref` 引用。若描述中的 [`Schema::Ref`] 所引用的类型不在描述内
+    /// （如取自递归类型的字段的描述），则引用以其名字为键、未在此定义的 `$defs` 项。
+    ///
+    /// ```
+    /// # use algtype::schema::Describe;
+    /// assert_eq!(
+    ///     <(bool, u8)>::schema().to_json_schema(),
+    ///     r#"{"type":"array","prefixItems":[{"type":"boolean"},{"type":"integer","minimum":0,"maximum":255}],"minItems":2,"maxItems":2}"#
+    /// );
+    /// assert_eq!(
+    ///     Option::<()>::schema().to_json_schema(),
+    ///     r##"{"$defs":{"Option":{"oneOf":[{"const":"None"},{"type":"object","properties":{"Some":{"type":"array","prefixItems":[{"type":"null"}],"minItems":1,"maxItems":1}},"required":["Some"],"additionalProperties":false}]}},"$ref":"#/$defs/Option"}"##
+    /// );
+    /// ```
+    pub fn to_json_schema(&self) -> String {
+        let mut defs = Defs::default();
+        self.refs(&mut defs.referenced);
+        let mut root = String::new();
+        self.write_json_schema(&mut root, &mut defs);
+        if defs.defs.is_empty() {
+            return root;
+        }
+        let mut out = String::from(r#"{"$defs":{"#);
+        for (i, (_, key, body)) in defs.defs.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            write_json_str(&mut out, key);
+            out.push(':');
+            out.push_str(body);
+        }
+        // 有定义时，根必然是对象（`$ref` 或含有字段者）
+        out.push_str("},");
+        out.push_str(&root[1..]);
+        out
+    }
+
+    /// 收集递归引用的类型
+    fn refs(&self, out: &mut Vec<&'static str>) {
+        match self {
+            Schema::Leaf(_) => {}
+            Schema::Seq(x) => x.refs(out),
+            Schema::Adt(x) => {
+                let fields = x.variants.iter().flat_map(|v| &v.fields);
+                fields.for_each(|f| f.schema.refs(out));
+            }
+            Schema::Ref { id, .. } => out.push(id),
+        }
+    }
+
+    fn write_json_schema(&self, out: &mut String, defs: &mut Defs) {
+        match self {
+            Schema::Leaf("unit") => out.push_str(r#"{"type":"null"}"#),
+            Schema::Leaf("bool") => out.push_str(r#"{"type":"boolean"}"#),
+            Schema::Leaf("string") => out.push_str(r#"{"type":"string"}"#),
+            Schema::Leaf("char") => {
+                out.push_str(r#"{"type":"string","minLength":1,"maxLength":1}"#)
+            }
+            Schema::Leaf("f32" | "f64") => out.push_str(r#"{"type":"number"}"#),
+            Schema::Leaf(name) => {
+                out.push_str(r#"{"type":"integer""#);
+                if let Some((min, max)) = int_range(name) {
+                    let _ = write!(out, r#","minimum":{min},"maximum":{max}"#);
+                }
+                out.push('}');
+            }
+            Schema::Seq(x) => {
+                out.push_str(r#"{"type":"array","items":"#);
+                x.write_json_schema(out, defs);
+                out.push('}');
+            }
+            Schema::Adt(x) if x.name.is_some() || defs.referenced.contains(&x.id) => {
+                let key = match defs.key(x.id) {
+                    Some(key) => key,
+                    None => {
+                        // 先登记，使内部的递归引用能找到它
+                        let key = defs.insert(x.id, x.name.unwrap_or("_"));
+                        let mut body = String::new();
+                        x.write_json_schema(&mut body, defs);
+                        defs.defs.iter_mut().find(|d| d.0 == x.id).unwrap().2 = body;
+                        key
+                    }
+                };
+                write_json_ref(out, &key);
+            }
+            Schema::Adt(x) => x.write_json_schema(out, defs),
+            Schema::Ref { id, name } => {
+                // 取自递归类型内部的描述不含被引用的类型，以其名字为键，与其完整描述中的键一致
+                let key = defs.key(id).unwrap_or_else(|| name.unwrap_or("_").into());
+                write_json_ref(out, &key);
+            }
+        }
+    }
+}
+
+/// 整数的范围
+fn int_range(name: &str) -> Option<(i128, u128)> {
+    Some(match name {
+        "u8" => (0, u8::MAX as u128),
+        "u16" => (0, u16::MAX as u128),
+        "u32" => (0, u32::MAX as u128),
+        "u64" => (0, u64::MAX as u128),
+        "u128" => (0, u128::MAX),
+        "usize" => (0, usize::MAX as u128),
+        "i8" => (i8::MIN as i128, i8::MAX as u128),
+        "i16" => (i16::MIN as i128, i16::MAX as u128),
+        "i32" => (i32::MIN as i128, i32::MAX as u128),
+        "i64" => (i64::MIN as i128, i64::MAX as u128),
+        "i128" => (i128::MIN, i128::MAX as u128),
+        "isize" => (isize::MIN as i128, isize::MAX as u128),
+        _ => return None,
+    })
+}
+
+/// JSON Schema 的 `$defs`
+#[derive(Default)]
+struct Defs {
+    /// 递归引用的类型
+    referenced: Vec<&'static str>,
+    /// 类型的标识、键及定义
+    defs: Vec<(&'static str, String, String)>,
+}
+
+impl Defs {
+    fn key(&self, id: &str) -> Option<String> {
+        self.defs.iter().find(|d| d.0 == id).map(|d| d.1.clone())
+    }
+
+    /// 登记类型，返回其键
+    fn insert(&mut self, id: &'static str, name: &str) -> String {
+        let mut key = String::from(name);
+        let mut n = 1;
+        while self.defs.iter().any(|d| d.1 == key) {
+            key = format!("{name}_{n}");
+            n += 1;
+        }
+        self.defs.push((id, key.clone(), String::new()));
+        key
+    }
+}
+
+fn write_json_ref(out: &mut String, key: &str) {
+    out.push_str(r#"{"$ref":"#);
+    write_json_str(out, &format!("#/$defs/{key}"));
+    out.push('}');
+}
+
+impl AdtSchema {
+    fn write_json_schema(&self, out: &mut String, defs: &mut Defs) {
+        if !self.is_enum {
+            match self.variants.first() {
+                Some(v) => v.write_json_schema(out, defs),
+                None => out.push_str("false"),
+            }
+            return;
+        }
+        out.push_str(r#"{"oneOf":["#);
+        for (i, v) in self.variants.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            let name = v.name.map_or_else(|| format!("{i}"), Into::into);
+            if v.fields.is_empty() {
+                out.push_str(r#"{"const":"#);
+                write_json_str(out, &name);
+                out.push('}');
+            } else {
+                out.push_str(r#"{"type":"object","properties":{"#);
+                write_json_str(out, &name);
+                out.push(':');
+                v.write_json_schema(out, defs);
+                out.push_str(r#"},"required":["#);
+                write_json_str(out, &name);
+                out.push_str(r#"],"additionalProperties":false}"#);
+            }
+        }
+        out.push_str("]}");
+    }
+}
+
+impl VariantSchema {
+    fn write_json_schema(&self, out: &mut String, defs: &mut Defs) {
+        match self.kind {
+            FieldsMeta::Unit => out.push_str(r#"{"type":"null"}"#),
+            FieldsMeta::Unnamed => {
+                out.push_str(r#"{"type":"array","prefixItems":["#);
+                for (i, f) in self.fields.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    f.schema.write_json_schema(out, defs);
+                }
+                let n = self.fields.len();
+                let _ = write!(out, r#"],"minItems":{n},"maxItems":{n}}}"#);
+            }
+            FieldsMeta::Named(_) => {
+                out.push_str(r#"{"type":"object","properties":{"#);
+                for (i, f) in self.fields.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    write_json_str(out, f.name.unwrap_or_default());
+                    out.push(':');
+                    f.schema.write_json_schema(out, defs);
+                }
+                out.push_str(r#"},"required":["#);
+                for (i, f) in self.fields.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    write_json_str(out, f.name.unwrap_or_default());
+                }
+                out.push_str(r#"],"additionalProperties":false}"#);
+            }
+        }
+    }
+}
+
+pub(crate) fn write_json_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+// IDL
+
+/// 打印为简单的 IDL
+///
+/// struct 和 enum 以类似 Rust 的语法打印，序列打印为 `[T]`，没有名字者打印为元组，
+/// 递归的引用打印为类型的名字。
+impl Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_idl(f, 0)
+    }
+}
+
+fn indent(f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+    (0..depth).try_for_each(|_| f.write_str("    "))
+}
+
+impl Schema {
+    fn write_idl(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        match self {
+            Schema::Leaf(name) => f.write_str(name),
+            Schema::Seq(x) => {
+                f.write_str("[")?;
+                x.write_idl(f, depth)?;
+                f.write_str("]")
+            }
+            Schema::Adt(x) => x.write_idl(f, depth),
+            Schema::Ref { name, .. } => f.write_str(name.unwrap_or("_")),
+        }
+    }
+}
+
+impl AdtSchema {
+    fn write_idl(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let Some(name) = self.name else {
+            // 没有名字者，如元组
+            f.write_str("(")?;
+            let fields = self.variants.iter().flat_map(|v| &v.fields);
+            for (i, x) in fields.enumerate() {
+                if i != 0 {
+                    f.write_str(", ")?;
+                }
+                x.schema.write_idl(f, depth)?;
+            }
+            return f.write_str(")");
+        };
+        if !self.is_enum {
+            f.write_str("struct ")?;
+            return match self.variants.first() {
+                Some(
+                    v @ VariantSchema {
+                        kind: FieldsMeta::Named(_),
+                        ..
+                    },
+                ) => {
+                    writeln!(f, "{name} {{")?;
+                    for x in &v.fields {
+                        indent(f, depth + 1)?;
+                        write!(f, "{}: ", x.name.unwrap_or("_"))?;
+                        x.schema.write_idl(f, depth + 1)?;
+                        f.write_str(",\n")?;
+                    }
+                    indent(f, depth)?;
+                    f.write_str("}")
+                }
+                Some(v) => v.write_idl(f, depth),
+                None => f.write_str(name),
+            };
+        }
+        writeln!(f, "enum {name} {{")?;
+        for v in &self.variants {
+            indent(f, depth + 1)?;
+            v.write_idl(f, depth + 1)?;
+            f.write_str(",\n")?;
+        }
+        indent(f, depth)?;
+        f.write_str("}")
+    }
+}
+
+impl VariantSchema {
+    fn write_idl(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        f.write_str(self.name.unwrap_or("_"))?;
+        match self.kind {
+            FieldsMeta::Unit => Ok(()),
+            FieldsMeta::Unnamed => {
+                f.write_str("(")?;
+                for (i, x) in self.fields.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    x.schema.write_idl(f, depth)?;
+                }
+                f.write_str(")")
+            }
+            FieldsMeta::Named(_) => {
+                f.write_str(" { ")?;
+                for (i, x) in self.fields.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: ", x.name.unwrap_or("_"))?;
+                    x.schema.write_idl(f, depth)?;
+                }
+                f.write_str(" }")
+            }
+        }
+    }
+}
+
+// ADT
+
+impl<T: Describe> GenericDescribe for Option<T> {}
+
+impl<T: Describe, E: Describe> GenericDescribe for Result<T, E> {}
+
+// 数组
+
+impl<T, const N: usize> GenericDescribe for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GDescribe,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    () => {};
+    ($($tys:ident)*) => {
+        impl <$($tys: Describe),*> GenericDescribe for ($($tys,)*) {}
+    };
+}
+
+visit_tuple!(impl_tuple);
+
+// 基本类型
+
+macro_rules! impl_leaf {
+    ($($ty:ty => $name:literal)*) => {$(
+        impl Describe for $ty {
+            fn describe(_stack: &mut Vec<&'static str>) -> Schema {
+                Schema::Leaf($name)
+            }
+        }
+    )*};
+}
+
+impl_leaf!(
+    () => "unit" bool => "bool" char => "char" String => "string" f32 => "f32" f64 => "f64"
+    u8 => "u8" u16 => "u16" u32 => "u32" u64 => "u64" u128 => "u128" usize => "usize"
+    i8 => "i8" i16 => "i16" i32 => "i32" i64 => "i64" i128 => "i128" isize => "isize"
+);
+
+impl<T: Describe> Describe for Vec<T> {
+    fn describe(stack: &mut Vec<&'static str>) -> Schema {
+        Schema::Seq(Box::new(T::describe(stack)))
+    }
+}
@@ -0,0 +1,132 @@
+//! 按类型选取字段
+//!
+//! 类似 frunk 的 `Selector`。对于只有一个变体的类型，[`GetByType`] 能取出其类型为 `T` 的唯一字段，
+//! [`HasType`] 可用作约束。字段的位置（见 [`index`](crate::index)）由类型推导得出。
+//! 若没有或有多个类型为 `T` 的字段，则会编译错误。
+//!
+//! ```
+//! # use algtype::{Generic, select::{GetByType, HasType}};
+//! struct Logger(Vec<String>);
+//! struct Database;
+//!
+//! #[derive(Generic)]
+//! struct Context {
+//!     db: Database,
+//!     logger: Logger,
+//!     retries: u32,
+//! }
+//!
+//! fn log<C: HasType<Logger, I>, I>(cx: &mut C, msg: &str) {
+//!     cx.get_by_type_mut::<Logger, _>().0.push(msg.into());
+//! }
+//!
+//! let mut cx = Context { db: Database, logger: Logger(vec![]), retries: 3 };
+//! log(&mut cx, "hello");
+//! assert_eq!(cx.logger.0, ["hello"]);
+//! assert_eq!(*cx.get_by_type::<u32, _>(), 3);
+//! ```
+//!
+//! ```compile_fail
+//! # use algtype::select::GetByType;
+//! // 有两个 `u8`，无法确定
+//! let _ = (1u8, 2u8).get_by_type::<u8, _>();
+//! ```
+
+use crate::{
+    flatten::Single,
+    index::{Here, There},
+    Generic, Product, Repr,
+};
+
+/// 在积类型中选取类型为 `T` 的字段，`I` 为其位置
+pub trait Selector<T, I>: Repr {
+    /// 从引用形式中选取
+    fn select<'a>(this: Self::Ref<'a>) -> &'a T
+    where
+        Self: 'a;
+    /// 从可变引用形式中选取
+    fn select_mut<'a>(this: Self::Mut<'a>) -> &'a mut T
+    where
+        Self: 'a;
+}
+
+impl<T, R: Repr> Selector<T, Here> for Product<T, R> {
+    fn select<'a>(this: Self::Ref<'a>) -> &'a T
+    where
+        Self: 'a,
+    {
+        this.0
+    }
+
+    fn select_mut<'a>(this: Self::Mut<'a>) -> &'a mut T
+    where
+        Self: 'a,
+    {
+        this.0
+    }
+}
+
+impl<H, R: Selector<T, I>, T, I> Selector<T, There<I>> for Product<H, R> {
+    fn select<'a>(this: Self::Ref<'a>) -> &'a T
+    where
+        Self: 'a,
+    {
+        R::select(this.1)
+    }
+
+    fn select_mut<'a>(this: Self::Mut<'a>) -> &'a mut T
+    where
+        Self: 'a,
+    {
+        R::select_mut(this.1)
+    }
+}
+
+/// 有类型为 `T` 的字段，`I` 为其位置
+///
+/// 为只有一个变体且有此字段的类型自动实现，可用作约束。
+pub trait HasType<T, I>: Generic {
+    /// 选取类型为 `T` 的字段
+    fn field(&self) -> &T;
+    /// 选取类型为 `T` 的字段的可变引用
+    fn field_mut(&mut self) -> &mut T;
+}
+
+impl<S: Generic, T, I> HasType<T, I> for S
+where
+    S::Repr: Single,
+    <S::Repr as Single>::Product: Selector<T, I>,
+{
+    fn field(&self) -> &T {
+        <S::Repr as Single>::Product::select(<S::Repr as Single>::unwrap_ref(self.as_repr()))
+    }
+
+    fn field_mut(&mut self) -> &mut T {
+        <S::Repr as Single>::Product::select_mut(<S::Repr as Single>::unwrap_mut(
+            self.as_mut_repr(),
+        ))
+    }
+}
+
+/// 按类型选取字段
+///
+/// 为所有实现 [`Generic`] 的类型自动实现。
+pub trait GetByType: Generic {
+    /// 选取类型为 `T` 的字段
+    fn get_by_type<T, I>(&self) -> &T
+    where
+        Self: HasType<T, I>,
+    {
+        self.field()
+    }
+
+    /// 选取类型为 `T` 的字段的可变引用
+    fn get_by_type_mut<T, I>(&mut self) -> &mut T
+    where
+        Self: HasType<T, I>,
+    {
+        self.field_mut()
+    }
+}
+
+impl<S: Generic> GetByType for S {}
@@ -0,0 +1,200 @@
+//! 编译期的结构指纹
+//!
+//! [`ShapeHash::SHAPE_HASH`] 在编译期由类型的结构计算：
+//!
+//! - 基本类型（整数、浮点数、`char`、字符串）各有固定的值，`usize` 与 `u64` 不同
+//! - 切片及其引用、`Box<[T]>`、`Vec<T>` 由元素类型计算；`&str`、`Box<str>`、`String` 与 `str` 相同
+//! - 实现 [`GenericShapeHash`] 者，由表示的结构和各字段类型的指纹计算；
+//!   若有 [`Generic::META`]，还包括类型、变体和字段的名字
+//!
+//! 增删、重排或改名变体和字段，或者字段类型（包括其内部的结构和泛型参数）改变时，它都会改变，
+//! 可用于在解码前检查持久化的数据或通信的对方是否一致。
+//! 递归的类型无法计算，会编译错误。
+//!
+//! ```
+//! # use algtype::{Generic, shape_hash::{GenericShapeHash, ShapeHash}};
+//! #[derive(Generic)]
+//! struct A<T> {
+//!     x: T,
+//!     y: i32,
+//! }
+//!
+//! impl<T: ShapeHash> GenericShapeHash for A<T> {}
+//!
+//! mod v2 {
+//!     # use algtype::{Generic, shape_hash::{GenericShapeHash, ShapeHash}};
+//!     #[derive(Generic)]
+//!     pub struct A<T> {
+//!         pub y: i32,
+//!         pub x: T,
+//!     }
+//!
+//!     impl<T: ShapeHash> GenericShapeHash for A<T> {}
+//! }
+//!
+//! const _: () = assert!(A::<i32>::SHAPE_HASH != v2::A::<i32>::SHAPE_HASH);
+//! assert_ne!(A::<u32>::SHAPE_HASH, A::<u64>::SHAPE_HASH);
+//! assert_ne!(<(i32, i32)>::SHAPE_HASH, <[bool; 2]>::SHAPE_HASH);
+//! assert_eq!(<(i32, i32)>::SHAPE_HASH, <[i32; 2]>::SHAPE_HASH);
+//! ```
+
+use crate::{visit_tuple, FieldsMeta, Generic, Meta, One, Product, Repr, Sum, Zero};
+
+const OFFSET: u64 = 0xcbf29ce484222325;
+
+/// 64 位 FNV-1a
+const fn fnv(mut h: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        h = (h ^ bytes[i] as u64).wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+    h
+}
+
+const fn mix(tag: &[u8], a: u64, b: u64) -> u64 {
+    let h = fnv(OFFSET, tag);
+    fnv(fnv(h, &a.to_le_bytes()), &b.to_le_bytes())
+}
+
+/// 名字的哈希，以 `h` 为初值
+const fn meta_hash(meta: &Meta, mut h: u64) -> u64 {
+    h = fnv(h, meta.name.as_bytes());
+    h = fnv(h, &[meta.is_enum as u8]);
+    let mut i = 0;
+    while i < meta.variants.len() {
+        let v = &meta.variants[i];
+        h = fnv(fnv(h, b"|"), v.name.as_bytes());
+        if let FieldsMeta::Named(names) = v.fields {
+            let mut j = 0;
+            while j < names.len() {
+                h = fnv(fnv(h, b","), names[j].as_bytes());
+                j += 1;
+            }
+        }
+        i += 1;
+    }
+    h
+}
+
+/// 有结构指纹者
+///
+/// 建议使用 [`GenericShapeHash`] 自动实现。
+pub trait ShapeHash {
+    /// 类型的结构的指纹
+    const SHAPE_HASH: u64;
+}
+
+/// 表示上的 [`ShapeHash`]
+pub trait GShapeHash: Repr {
+    /// 表示的结构的指纹
+    const G_SHAPE_HASH: u64;
+}
+
+impl GShapeHash for Zero {
+    const G_SHAPE_HASH: u64 = mix(b"0", 0, 0);
+}
+
+impl GShapeHash for One {
+    const G_SHAPE_HASH: u64 = mix(b"1", 0, 0);
+}
+
+impl<T: GShapeHash, R: GShapeHash> GShapeHash for Sum<T, R> {
+    const G_SHAPE_HASH: u64 = mix(b"+", T::G_SHAPE_HASH, R::G_SHAPE_HASH);
+}
+
+impl<T: ShapeHash, R: GShapeHash> GShapeHash for Product<T, R> {
+    const G_SHAPE_HASH: u64 = mix(b"*", T::SHAPE_HASH, R::G_SHAPE_HASH);
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericShapeHash`]，将会自动实现 [`ShapeHash`]。
+pub trait GenericShapeHash: Generic
+where
+    Self::Repr: GShapeHash,
+{
+}
+
+impl<T: GenericShapeHash> ShapeHash for T
+where
+    T::Repr: GShapeHash,
+{
+    const SHAPE_HASH: u64 = match T::META {
+        Some(meta) => meta_hash(meta, T::Repr::G_SHAPE_HASH),
+        None => T::Repr::G_SHAPE_HASH,
+    };
+}
+
+// ADT
+
+impl<T: ShapeHash> GenericShapeHash for Option<T> {}
+
+impl<T: ShapeHash, E: ShapeHash> GenericShapeHash for Result<T, E> {}
+
+impl GenericShapeHash for bool {}
+
+// 数组
+
+impl<T, const N: usize> GenericShapeHash for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GShapeHash,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    ($($tys:ident)*) => {
+        impl <$($tys: ShapeHash),*> GenericShapeHash for ($($tys,)*) {}
+    };
+}
+
+visit_tuple!(impl_tuple);
+
+// 基本类型
+
+macro_rules! impl_leaf {
+    ($($ty:ty)*) => {$(
+        impl ShapeHash for $ty {
+            const SHAPE_HASH: u64 = fnv(OFFSET, stringify!($ty).as_bytes());
+        }
+    )*};
+}
+
+impl_leaf!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64 char str);
+
+// 序列和指针
+
+impl<T: ShapeHash> ShapeHash for [T] {
+    const SHAPE_HASH: u64 = mix(b"[]", T::SHAPE_HASH, 0);
+}
+
+impl<T: ShapeHash> ShapeHash for &[T] {
+    const SHAPE_HASH: u64 = <[T]>::SHAPE_HASH;
+}
+
+impl ShapeHash for &str {
+    const SHAPE_HASH: u64 = str::SHAPE_HASH;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ShapeHash> ShapeHash for alloc::boxed::Box<[T]> {
+    const SHAPE_HASH: u64 = <[T]>::SHAPE_HASH;
+}
+
+#[cfg(feature = "alloc")]
+impl ShapeHash for alloc::boxed::Box<str> {
+    const SHAPE_HASH: u64 = str::SHAPE_HASH;
+}
+
+#[cfg(feature = "alloc")]
+impl ShapeHash for alloc::string::String {
+    const SHAPE_HASH: u64 = str::SHAPE_HASH;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ShapeHash> ShapeHash for alloc::vec::Vec<T> {
+    const SHAPE_HASH: u64 = <[T]>::SHAPE_HASH;
+}
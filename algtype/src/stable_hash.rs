@@ -0,0 +1,237 @@
+//! 稳定的结构哈希
+//!
+//! [`core::hash::Hash`] 的结果随平台和版本而变，`usize` 的宽度也不一。
+//! [`StableHash`] 使用固定的算法和编码，结果可以持久保存，例如作为缓存的键。
+//!
+//! 算法是 64 位的 FNV-1a，编码如下（整数均为小端序）：
+//!
+//! - 整数按其宽度编码，`usize`、`isize` 按 64 位编码
+//! - `char` 编码为 `u32`，浮点数编码其位
+//! - 字符串和切片先编码长度（`u64`），再依次编码元素
+//! - 实现 [`GenericStableHash`] 者，先编码变体的序号（`u32`），
+//!   再依次编码每个字段的位置（`u32`）及其值。`bool`、`Option`、元组等都是如此
+
+use crate::{visit_tuple, Generic, One, Product, Repr, Sum, Zero};
+
+/// 64 位 FNV-1a 哈希器
+///
+/// 与 [`core::hash::Hasher`] 不同，它不会写入平台相关的内容。
+///
+/// ```
+/// # use algtype::stable_hash::StableHasher;
+/// let mut h = StableHasher::new();
+/// h.write(b"a");
+/// assert_eq!(h.finish(), 0xaf63dc4c8601ec8c);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    /// 创建初始状态的哈希器
+    pub const fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+
+    /// 写入字节
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ b as u64).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// 获取哈希值
+    pub const fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 可以稳定地哈希者
+///
+/// 建议使用 [`GenericStableHash`] 自动实现。
+pub trait StableHash {
+    /// 把数据写入哈希器
+    fn stable_hash(&self, h: &mut StableHasher);
+}
+
+/// 计算数据的稳定哈希
+///
+/// ```
+/// # use algtype::stable_hash::{stable_hash, StableHasher};
+/// let mut h = StableHasher::new();
+/// h.write(&[1, 0, 0, 0]);
+/// assert_eq!(stable_hash(&1u32), h.finish());
+/// assert_eq!(stable_hash(&1usize), stable_hash(&1u64));
+/// assert_ne!(stable_hash(&Some(1)), stable_hash(&None::<i32>));
+/// // 只看结构，不看类型
+/// assert_eq!(stable_hash(&Some(1)), stable_hash(&Ok::<_, ()>(1)));
+/// ```
+pub fn stable_hash<T: StableHash + ?Sized>(x: &T) -> u64 {
+    let mut h = StableHasher::new();
+    x.stable_hash(&mut h);
+    h.finish()
+}
+
+/// 表示上的 [`StableHash`]
+///
+/// `index` 对于和类型是变体的序号，对于积类型是字段的位置。
+pub trait GStableHash: Repr {
+    fn g_stable_hash(this: &Self::Ref<'_>, h: &mut StableHasher, index: u32);
+}
+
+impl GStableHash for Zero {
+    fn g_stable_hash(this: &Self::Ref<'_>, _h: &mut StableHasher, _index: u32) {
+        match *this {}
+    }
+}
+
+impl GStableHash for One {
+    fn g_stable_hash(_this: &Self::Ref<'_>, _h: &mut StableHasher, _index: u32) {}
+}
+
+impl<T: GStableHash, R: GStableHash> GStableHash for Sum<T, R> {
+    fn g_stable_hash(this: &Self::Ref<'_>, h: &mut StableHasher, index: u32) {
+        match this {
+            Sum::This(x) => {
+                index.stable_hash(h);
+                T::g_stable_hash(x, h, 0);
+            }
+            Sum::Next(x) => R::g_stable_hash(x, h, index + 1),
+        }
+    }
+}
+
+impl<T: StableHash, R: GStableHash> GStableHash for Product<T, R> {
+    fn g_stable_hash(this: &Self::Ref<'_>, h: &mut StableHasher, index: u32) {
+        index.stable_hash(h);
+        this.0.stable_hash(h);
+        R::g_stable_hash(&this.1, h, index + 1);
+    }
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericStableHash`]，将会自动实现 [`StableHash`]。
+///
+/// ```
+/// # use algtype::{Generic, stable_hash::{stable_hash, GenericStableHash}};
+/// #[derive(Generic)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl GenericStableHash for Point {}
+///
+/// assert_eq!(stable_hash(&Point { x: 1, y: 2 }), stable_hash(&(1, 2)));
+/// ```
+pub trait GenericStableHash: Generic
+where
+    Self::Repr: GStableHash,
+{
+}
+
+impl<T: GenericStableHash> StableHash for T
+where
+    T::Repr: GStableHash,
+{
+    fn stable_hash(&self, h: &mut StableHasher) {
+        T::Repr::g_stable_hash(&self.as_repr(), h, 0);
+    }
+}
+
+// ADT
+
+impl<T: StableHash> GenericStableHash for Option<T> {}
+
+impl<T: StableHash, E: StableHash> GenericStableHash for Result<T, E> {}
+
+impl GenericStableHash for bool {}
+
+// 数组
+
+impl<T, const N: usize> GenericStableHash for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GStableHash,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    ($($tys:ident)*) => {
+        impl <$($tys: StableHash),*> GenericStableHash for ($($tys,)*) {}
+    };
+}
+
+visit_tuple!(impl_tuple);
+
+// 各种数字
+
+macro_rules! impl_number {
+    ($($ty:ty => $as:ty)*) => {$(
+        impl StableHash for $ty {
+            #[inline]
+            fn stable_hash(&self, h: &mut StableHasher) {
+                h.write(&(*self as $as).to_le_bytes());
+            }
+        }
+    )*};
+}
+
+impl_number!(
+    u8 => u8 u16 => u16 u32 => u32 u64 => u64 u128 => u128 usize => u64
+    i8 => i8 i16 => i16 i32 => i32 i64 => i64 i128 => i128 isize => i64
+    char => u32
+);
+
+impl StableHash for f32 {
+    #[inline]
+    fn stable_hash(&self, h: &mut StableHasher) {
+        self.to_bits().stable_hash(h);
+    }
+}
+
+impl StableHash for f64 {
+    #[inline]
+    fn stable_hash(&self, h: &mut StableHasher) {
+        self.to_bits().stable_hash(h);
+    }
+}
+
+// 序列
+
+impl<T: StableHash> StableHash for [T] {
+    fn stable_hash(&self, h: &mut StableHasher) {
+        self.len().stable_hash(h);
+        self.iter().for_each(|x| x.stable_hash(h));
+    }
+}
+
+impl StableHash for str {
+    fn stable_hash(&self, h: &mut StableHasher) {
+        self.len().stable_hash(h);
+        h.write(self.as_bytes());
+    }
+}
+
+impl<T: StableHash> StableHash for &[T] {
+    fn stable_hash(&self, h: &mut StableHasher) {
+        (**self).stable_hash(h);
+    }
+}
+
+impl StableHash for &str {
+    fn stable_hash(&self, h: &mut StableHasher) {
+        (**self).stable_hash(h);
+    }
+}
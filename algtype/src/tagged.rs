@@ -0,0 +1,606 @@
+//! 带标签、可演进的二进制编码
+//!
+//! 类似 protobuf，每个字段带有其标签和线格式，解码时跳过未知的字段，缺少的字段取默认值。
+//! 因此新旧版本的类型能互相读取对方的数据，只要：
+//!
+//! - 新字段加在变体的末尾，不删除或重排已有的字段（标签是字段的位置）
+//! - 新变体加在末尾（变体以序号区分）；读到未知的变体是错误
+//!
+//! 实现 [`Tagged`] 者可以编码，可使用 [`GenericTagged`] 自动实现。编码如下：
+//!
+//! - 变长整数（varint）是 LEB128，有符号整数先经 zigzag 变换
+//! - 字段的键是 varint `tag << 3 | wire`，其后是值。标签从 1 开始，线格式见 [`Wire`]
+//! - `bool`、整数、`char` 编码为 varint，`f32`、`f64` 编码为小端序的定长值
+//! - 字符串编码为其长度（varint）和 UTF-8 字节
+//! - `Vec` 编码为其长度和各元素依次的编码，元素不带键
+//! - 实现 [`GenericTagged`] 者，编码为其长度和内容；内容是变体的序号（varint）和各字段
+//!
+//! 缺少的字段取 [`Tagged::default_value`]：基本类型取其 `Default`，
+//! 实现 [`GenericTagged`] 者取第一个变体，其字段取默认值。这与 protobuf 的枚举一致，
+//! 例如 `Option` 的默认值是 `None`。
+//! 类型可以实现 [`GenericTagged::tagged_default`] 以使用自己的默认值，如其 `Default`：
+//! 此时缺少的字段取该默认值中对应的字段，整个值缺少时取该默认值。
+//!
+//! 不支持递归的类型，否则会无限递归。
+
+use crate::{visit_tuple, Generic, One, Product, Repr, Sum, Zero};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// 线格式，决定如何跳过未知的值
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Wire {
+    /// 变长整数
+    Varint = 0,
+    /// 8 字节
+    Fixed64 = 1,
+    /// 长度（varint）及其后的字节
+    Len = 2,
+    /// 4 字节
+    Fixed32 = 5,
+}
+
+impl Wire {
+    fn from_bits(bits: u64) -> Option<Self> {
+        Some(match bits {
+            0 => Wire::Varint,
+            1 => Wire::Fixed64,
+            2 => Wire::Len,
+            5 => Wire::Fixed32,
+            _ => return None,
+        })
+    }
+}
+
+/// 解码的错误
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// 数据意外结束
+    Eof,
+    /// 数据的末尾有多余的字节
+    TrailingBytes,
+    /// 变长整数超出范围
+    Overflow,
+    /// 未知的线格式
+    UnknownWire(u8),
+    /// 字段的线格式与类型不符
+    WireMismatch {
+        tag: u64,
+        expected: Wire,
+        found: Wire,
+    },
+    /// 未知的变体
+    UnknownVariant(u64),
+    /// 缺少字段，且字段的类型没有默认值
+    MissingField(u64),
+    /// 值不合法，如 `bool` 不是 0 或 1、字符串不是 UTF-8
+    Invalid(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Eof => f.write_str("unexpected end of data"),
+            Error::TrailingBytes => f.write_str("trailing bytes"),
+            Error::Overflow => f.write_str("varint out of range"),
+            Error::UnknownWire(w) => write!(f, "unknown wire type {w}"),
+            Error::WireMismatch {
+                tag,
+                expected,
+                found,
+            } => write!(f, "field {tag}: expected {expected:?}, found {found:?}"),
+            Error::UnknownVariant(i) => write!(f, "unknown variant {i}"),
+            Error::MissingField(tag) => write!(f, "missing field {tag}"),
+            Error::Invalid(what) => write!(f, "invalid {what}"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// 写入变长整数
+pub fn write_varint(out: &mut Vec<u8>, mut x: u128) {
+    while x >= 0x80 {
+        out.push(x as u8 | 0x80);
+        x >>= 7;
+    }
+    out.push(x as u8);
+}
+
+/// 读取变长整数
+pub fn read_varint(r: &mut &[u8]) -> Result<u128, Error> {
+    let mut x = 0u128;
+    for shift in (0..128).step_by(7) {
+        let (&b, rest) = r.split_first().ok_or(Error::Eof)?;
+        *r = rest;
+        let bits = (b & 0x7f) as u128;
+        if shift > 0 && bits >> (128 - shift) != 0 {
+            return Err(Error::Overflow);
+        }
+        x |= bits << shift;
+        if b < 0x80 {
+            return Ok(x);
+        }
+    }
+    Err(Error::Overflow)
+}
+
+fn read_bytes<'a>(r: &mut &'a [u8], n: usize) -> Result<&'a [u8], Error> {
+    if r.len() < n {
+        return Err(Error::Eof);
+    }
+    let (bytes, rest) = r.split_at(n);
+    *r = rest;
+    Ok(bytes)
+}
+
+/// 读取长度及其后的字节
+fn read_len<'a>(r: &mut &'a [u8]) -> Result<&'a [u8], Error> {
+    let n = read_varint(r)?.try_into().map_err(|_| Error::Eof)?;
+    read_bytes(r, n)
+}
+
+/// 写入长度及 `f` 写入的字节
+fn write_len(out: &mut Vec<u8>, f: impl FnOnce(&mut Vec<u8>)) {
+    let mut body = Vec::new();
+    f(&mut body);
+    write_varint(out, body.len() as u128);
+    out.extend_from_slice(&body);
+}
+
+/// 可编码者
+///
+/// 建议使用 [`GenericTagged`] 自动实现。
+pub trait Tagged: Sized {
+    /// 值的线格式
+    const WIRE: Wire;
+    /// 写入值，不含键
+    fn encode_value(&self, out: &mut Vec<u8>);
+    /// 读取值，不含键
+    fn decode_value(r: &mut &[u8]) -> Result<Self, Error>;
+    /// 字段缺少时的默认值
+    fn default_value() -> Option<Self> {
+        None
+    }
+}
+
+/// 编码
+pub fn to_bytes<T: Tagged>(x: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    x.encode_value(&mut out);
+    out
+}
+
+/// 解码，须用完所有字节
+pub fn from_bytes<T: Tagged>(mut bytes: &[u8]) -> Result<T, Error> {
+    let x = T::decode_value(&mut bytes)?;
+    match bytes.is_empty() {
+        true => Ok(x),
+        false => Err(Error::TrailingBytes),
+    }
+}
+
+/// 变体中的一个字段，值含长度前缀（若有）
+#[derive(Clone, Copy, Debug)]
+pub struct Field<'a> {
+    tag: u64,
+    wire: Wire,
+    value: &'a [u8],
+}
+
+/// 读取变体的所有字段
+fn read_fields<'a>(mut r: &'a [u8]) -> Result<Vec<Field<'a>>, Error> {
+    let mut fields = Vec::new();
+    while !r.is_empty() {
+        let key = u64::try_from(read_varint(&mut r)?).map_err(|_| Error::Overflow)?;
+        let wire = Wire::from_bits(key & 7).ok_or(Error::UnknownWire((key & 7) as u8))?;
+        let start = r;
+        match wire {
+            Wire::Varint => read_varint(&mut r).map(|_| ())?,
+            Wire::Fixed64 => read_bytes(&mut r, 8).map(|_| ())?,
+            Wire::Len => read_len(&mut r).map(|_| ())?,
+            Wire::Fixed32 => read_bytes(&mut r, 4).map(|_| ())?,
+        }
+        let value = &start[..start.len() - r.len()];
+        fields.push(Field {
+            tag: key >> 3,
+            wire,
+            value,
+        });
+    }
+    Ok(fields)
+}
+
+/// 表示上的 [`Tagged`]，用于和类型
+pub trait GTagged: Repr + Sized {
+    /// 变体的数量
+    const COUNT: u64;
+    /// 写入变体的序号及其字段，`index` 为当前变体的序号
+    fn g_encode(this: &Self::Ref<'_>, index: u64, out: &mut Vec<u8>);
+    /// 读取第 `target` 个变体，缺少的字段取 `default` 中对应的字段（若变体相同）
+    fn g_decode(target: u64, fields: &[Field<'_>], default: Option<Self>) -> Result<Self, Error>;
+    /// 第一个变体，字段取默认值
+    fn g_default() -> Option<Self>;
+}
+
+/// 表示上的 [`Tagged`]，用于积类型，`tag` 为当前字段的标签
+pub trait GTaggedFields: Repr + Sized {
+    fn g_encode(this: &Self::Ref<'_>, tag: u64, out: &mut Vec<u8>);
+    fn g_decode(tag: u64, fields: &[Field<'_>], default: Option<Self>) -> Result<Self, Error>;
+    fn g_default() -> Option<Self>;
+}
+
+impl GTagged for Zero {
+    const COUNT: u64 = 0;
+
+    fn g_encode(this: &Self::Ref<'_>, _index: u64, _out: &mut Vec<u8>) {
+        match *this {}
+    }
+
+    fn g_decode(
+        _target: u64,
+        _fields: &[Field<'_>],
+        _default: Option<Self>,
+    ) -> Result<Self, Error> {
+        unreachable!("variant index out of range")
+    }
+
+    fn g_default() -> Option<Self> {
+        None
+    }
+}
+
+impl<T: GTaggedFields, R: GTagged> GTagged for Sum<T, R> {
+    const COUNT: u64 = 1 + R::COUNT;
+
+    fn g_encode(this: &Self::Ref<'_>, index: u64, out: &mut Vec<u8>) {
+        match this {
+            Sum::This(x) => {
+                write_varint(out, index.into());
+                T::g_encode(x, 1, out);
+            }
+            Sum::Next(x) => R::g_encode(x, index + 1, out),
+        }
+    }
+
+    fn g_decode(target: u64, fields: &[Field<'_>], default: Option<Self>) -> Result<Self, Error> {
+        match (target, default) {
+            (0, Some(Sum::This(d))) => T::g_decode(1, fields, Some(d)).map(Sum::This),
+            (0, _) => T::g_decode(1, fields, None).map(Sum::This),
+            (i, Some(Sum::Next(d))) => R::g_decode(i - 1, fields, Some(d)).map(Sum::Next),
+            (i, _) => R::g_decode(i - 1, fields, None).map(Sum::Next),
+        }
+    }
+
+    fn g_default() -> Option<Self> {
+        T::g_default().map(Sum::This)
+    }
+}
+
+impl GTaggedFields for One {
+    fn g_encode(_this: &Self::Ref<'_>, _tag: u64, _out: &mut Vec<u8>) {}
+
+    fn g_decode(_tag: u64, _fields: &[Field<'_>], _default: Option<Self>) -> Result<Self, Error> {
+        Ok(One)
+    }
+
+    fn g_default() -> Option<Self> {
+        Some(One)
+    }
+}
+
+impl<T: Tagged, R: GTaggedFields> GTaggedFields for Product<T, R> {
+    fn g_encode(this: &Self::Ref<'_>, tag: u64, out: &mut Vec<u8>) {
+        write_varint(out, (tag << 3 | T::WIRE as u64).into());
+        this.0.encode_value(out);
+        R::g_encode(&this.1, tag + 1, out);
+    }
+
+    fn g_decode(tag: u64, fields: &[Field<'_>], default: Option<Self>) -> Result<Self, Error> {
+        let (d, rest) = default.map_or((None, None), |Product(d, rest)| (Some(d), Some(rest)));
+        // 与 protobuf 相同，重复的字段以最后一个为准
+        let x = match fields.iter().rev().find(|f| f.tag == tag) {
+            Some(f) if f.wire != T::WIRE => {
+                return Err(Error::WireMismatch {
+                    tag,
+                    expected: T::WIRE,
+                    found: f.wire,
+                })
+            }
+            Some(f) => from_bytes(f.value)?,
+            None => d
+                .or_else(T::default_value)
+                .ok_or(Error::MissingField(tag))?,
+        };
+        Ok(Product(x, R::g_decode(tag + 1, fields, rest)?))
+    }
+
+    fn g_default() -> Option<Self> {
+        Some(Product(T::default_value()?, R::g_default()?))
+    }
+}
+
+/// 基于 [`Generic`] 自动实现
+///
+/// 若某类型实现了 [`Generic`] 和 [`GenericTagged`]，将会自动实现 [`Tagged`]。
+///
+/// ```
+/// # use algtype::{Generic, tagged::{self, GenericTagged}};
+/// mod v1 {
+///     # use algtype::{Generic, tagged::GenericTagged};
+///     #[derive(Generic, Debug, PartialEq)]
+///     pub struct User { pub id: u32, pub name: String }
+///     impl GenericTagged for User {}
+/// }
+///
+/// mod v2 {
+///     # use algtype::{Generic, tagged::GenericTagged};
+///     #[derive(Generic, Debug, PartialEq)]
+///     pub struct User { pub id: u32, pub name: String, pub email: Option<String> }
+///     impl GenericTagged for User {}
+/// }
+///
+/// let old = v1::User { id: 7, name: "ann".into() };
+/// let new: v2::User = tagged::from_bytes(&tagged::to_bytes(&old)).unwrap();
+/// assert_eq!(new, v2::User { id: 7, name: "ann".into(), email: None });
+///
+/// let new = v2::User { email: Some("a@b".into()), ..new };
+/// let old: v1::User = tagged::from_bytes(&tagged::to_bytes(&new)).unwrap();
+/// assert_eq!(old, v1::User { id: 7, name: "ann".into() });
+/// ```
+pub trait GenericTagged: Generic
+where
+    Self::Repr: GTagged,
+{
+    /// 自己的默认值，缺少的字段取其中对应的字段
+    ///
+    /// 默认为 `None`，即各字段取其类型的默认值。解码时每次都会调用。
+    ///
+    /// ```
+    /// # use algtype::{Generic, tagged::{self, GenericTagged}};
+    /// #[derive(Generic, Debug, PartialEq)]
+    /// struct Config { host: String, port: u16 }
+    ///
+    /// impl Default for Config {
+    ///     fn default() -> Self {
+    ///         Config { host: "localhost".into(), port: 8080 }
+    ///     }
+    /// }
+    ///
+    /// impl GenericTagged for Config {
+    ///     fn tagged_default() -> Option<Self> {
+    ///         Some(Self::default())
+    ///     }
+    /// }
+    ///
+    /// let old = tagged::to_bytes(&("a".to_string(),));
+    /// let new: Config = tagged::from_bytes(&old).unwrap();
+    /// assert_eq!(new, Config { host: "a".into(), port: 8080 });
+    /// ```
+    fn tagged_default() -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+impl<T: GenericTagged> Tagged for T
+where
+    T::Repr: GTagged,
+{
+    const WIRE: Wire = Wire::Len;
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        write_len(out, |out| T::Repr::g_encode(&self.as_repr(), 0, out));
+    }
+
+    fn decode_value(r: &mut &[u8]) -> Result<Self, Error> {
+        let mut body = read_len(r)?;
+        let index = read_varint(&mut body)?;
+        if index >= T::Repr::COUNT as u128 {
+            return Err(Error::UnknownVariant(index.try_into().unwrap_or(u64::MAX)));
+        }
+        let fields = read_fields(body)?;
+        let default = T::tagged_default().map(T::into_repr);
+        T::Repr::g_decode(index as u64, &fields, default).map(T::from_repr)
+    }
+
+    fn default_value() -> Option<Self> {
+        T::tagged_default().or_else(|| T::Repr::g_default().map(T::from_repr))
+    }
+}
+
+// ADT
+
+impl<T: Tagged> GenericTagged for Option<T> {}
+
+impl<T: Tagged, E: Tagged> GenericTagged for Result<T, E> {}
+
+// 数组
+
+impl<T, const N: usize> GenericTagged for [T; N]
+where
+    Self: Generic,
+    Self::Repr: GTagged,
+{
+}
+
+// 元组
+
+macro_rules! impl_tuple {
+    () => {};
+    ($($tys:ident)*) => {
+        impl <$($tys: Tagged),*> GenericTagged for ($($tys,)*) {}
+    };
+}
+
+visit_tuple!(impl_tuple);
+
+// 基本类型
+
+impl Tagged for () {
+    const WIRE: Wire = Wire::Varint;
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        out.push(0);
+    }
+
+    fn decode_value(r: &mut &[u8]) -> Result<Self, Error> {
+        match read_varint(r)? {
+            0 => Ok(()),
+            _ => Err(Error::Invalid("unit")),
+        }
+    }
+
+    fn default_value() -> Option<Self> {
+        Some(())
+    }
+}
+
+impl Tagged for bool {
+    const WIRE: Wire = Wire::Varint;
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn decode_value(r: &mut &[u8]) -> Result<Self, Error> {
+        match read_varint(r)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::Invalid("bool")),
+        }
+    }
+
+    fn default_value() -> Option<Self> {
+        Some(false)
+    }
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty)*) => {$(
+        impl Tagged for $ty {
+            const WIRE: Wire = Wire::Varint;
+
+            fn encode_value(&self, out: &mut Vec<u8>) {
+                write_varint(out, *self as u128);
+            }
+
+            fn decode_value(r: &mut &[u8]) -> Result<Self, Error> {
+                read_varint(r)?.try_into().map_err(|_| Error::Overflow)
+            }
+
+            fn default_value() -> Option<Self> {
+                Some(0)
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8 u16 u32 u64 u128 usize);
+
+macro_rules! impl_signed {
+    ($($ty:ty)*) => {$(
+        impl Tagged for $ty {
+            const WIRE: Wire = Wire::Varint;
+
+            fn encode_value(&self, out: &mut Vec<u8>) {
+                let x = *self as i128;
+                write_varint(out, ((x << 1) ^ (x >> 127)) as u128);
+            }
+
+            fn decode_value(r: &mut &[u8]) -> Result<Self, Error> {
+                let x = read_varint(r)?;
+                let x = (x >> 1) as i128 ^ -((x & 1) as i128);
+                x.try_into().map_err(|_| Error::Overflow)
+            }
+
+            fn default_value() -> Option<Self> {
+                Some(0)
+            }
+        }
+    )*};
+}
+
+impl_signed!(i8 i16 i32 i64 i128 isize);
+
+macro_rules! impl_float {
+    ($($ty:ty => $wire:ident $n:literal)*) => {$(
+        impl Tagged for $ty {
+            const WIRE: Wire = Wire::$wire;
+
+            fn encode_value(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn decode_value(r: &mut &[u8]) -> Result<Self, Error> {
+                let bytes = read_bytes(r, $n)?;
+                Ok(<$ty>::from_le_bytes(bytes.try_into().unwrap()))
+            }
+
+            fn default_value() -> Option<Self> {
+                Some(0.0)
+            }
+        }
+    )*};
+}
+
+impl_float!(f32 => Fixed32 4 f64 => Fixed64 8);
+
+impl Tagged for char {
+    const WIRE: Wire = Wire::Varint;
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        write_varint(out, *self as u128);
+    }
+
+    fn decode_value(r: &mut &[u8]) -> Result<Self, Error> {
+        let x = u32::try_from(read_varint(r)?).map_err(|_| Error::Invalid("char"))?;
+        char::from_u32(x).ok_or(Error::Invalid("char"))
+    }
+
+    fn default_value() -> Option<Self> {
+        Some('\0')
+    }
+}
+
+impl Tagged for String {
+    const WIRE: Wire = Wire::Len;
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len() as u128);
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode_value(r: &mut &[u8]) -> Result<Self, Error> {
+        let bytes = read_len(r)?;
+        let s = core::str::from_utf8(bytes).map_err(|_| Error::Invalid("UTF-8"))?;
+        Ok(s.into())
+    }
+
+    fn default_value() -> Option<Self> {
+        Some(String::new())
+    }
+}
+
+impl<T: Tagged> Tagged for Vec<T> {
+    const WIRE: Wire = Wire::Len;
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        write_len(out, |out| self.iter().for_each(|x| x.encode_value(out)));
+    }
+
+    fn decode_value(r: &mut &[u8]) -> Result<Self, Error> {
+        let mut body = read_len(r)?;
+        let mut xs = Vec::new();
+        while !body.is_empty() {
+            xs.push(T::decode_value(&mut body)?);
+        }
+        Ok(xs)
+    }
+
+    fn default_value() -> Option<Self> {
+        Some(Vec::new())
+    }
+}
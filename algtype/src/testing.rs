@@ -0,0 +1,58 @@
+//! 检验 [`Generic`] 的实现
+//!
+//! 需启用 `testing` 特性。手写 [`Generic`] 的实现时，可用于测试。
+//!
+//! 检验的规律是：`into_repr` 与 `from_repr` 互逆，
+//! 且 `as_repr`、`as_mut_repr` 与 `into_repr` 后取引用的结果一致。
+
+use crate::{Generic, Repr};
+use core::fmt::Debug;
+use proptest::{arbitrary::Arbitrary, proptest};
+
+/// 对某个值检验规律
+///
+/// ```
+/// # use algtype::testing::assert_refl;
+/// assert_refl(Some((1, true)));
+/// ```
+pub fn assert_refl<T: Generic<Repr = R> + PartialEq + Debug + Clone, R: Repr>(mut x: T)
+where
+    for<'a> R::Ref<'a>: PartialEq + Debug,
+    for<'a> R::Mut<'a>: PartialEq + Debug,
+{
+    let mut repr = x.clone().into_repr();
+    assert_eq!(x.as_repr(), repr.as_ref());
+    assert_eq!(x.as_mut_repr(), repr.as_mut_ref());
+    assert_eq!(x, T::from_repr(repr));
+}
+
+/// 对每个值检验规律
+///
+/// 可以传入所有的值，以穷举地检验。
+///
+/// ```
+/// # use algtype::testing::assert_refl_all;
+/// assert_refl_all([None, Some(false), Some(true)]);
+/// ```
+pub fn assert_refl_all<T: Generic<Repr = R> + PartialEq + Debug + Clone, R: Repr>(
+    xs: impl IntoIterator<Item = T>,
+) where
+    for<'a> R::Ref<'a>: PartialEq + Debug,
+    for<'a> R::Mut<'a>: PartialEq + Debug,
+{
+    xs.into_iter().for_each(assert_refl);
+}
+
+/// 对随机的值检验规律
+///
+/// ```
+/// # use algtype::testing::check_refl;
+/// check_refl::<Result<u8, (i32, char)>, _>();
+/// ```
+pub fn check_refl<T: Generic<Repr = R> + PartialEq + Debug + Clone + Arbitrary, R: Repr>()
+where
+    for<'a> R::Ref<'a>: PartialEq + Debug,
+    for<'a> R::Mut<'a>: PartialEq + Debug,
+{
+    proptest!(|(x: T)| assert_refl(x));
+}
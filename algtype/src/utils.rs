@@ -1,6 +1,6 @@
 //! [`Generic`] 上可用的工具
 
-use crate::{Generic, One, Product, Sum, Zero};
+use crate::{Generic, One, Product, Repr, Sum, Zero};
 
 /// 构造 newtype
 ///
@@ -15,6 +15,164 @@ pub fn singleton<T, U: Generic<Repr = Sum<Product<T, One>, Zero>>>(x: T) -> U {
     U::from_repr(Sum::This(Product(x, One)))
 }
 
+/// newtype，即只有一个字段的类型
+///
+/// 表示为 `Sum<Product<T, One>, Zero>` 者自动实现之。
+///
+/// ```
+/// # use algtype::{Generic, utils::Newtype};
+/// #[derive(Generic, Debug, PartialEq)]
+/// struct UserId(u64);
+///
+/// let mut id = UserId::from_inner(1);
+/// *id.as_inner_mut() += 1;
+/// assert_eq!(id.as_inner(), &2);
+/// let id = id.map_inner(|x| x * 10);
+/// assert_eq!(id.into_inner(), 20);
+/// ```
+pub trait Newtype: Sized {
+    /// 里面的类型
+    type Inner;
+    /// 从里面的值构造，同 [`singleton`]
+    fn from_inner(x: Self::Inner) -> Self;
+    /// 取出里面的值
+    fn into_inner(self) -> Self::Inner;
+    /// 里面的值的引用
+    fn as_inner(&self) -> &Self::Inner;
+    /// 里面的值的可变引用
+    fn as_inner_mut(&mut self) -> &mut Self::Inner;
+    /// 映射里面的值
+    fn map_inner(self, f: impl FnOnce(Self::Inner) -> Self::Inner) -> Self {
+        Self::from_inner(f(self.into_inner()))
+    }
+}
+
+impl<T, U: Generic<Repr = Sum<Product<T, One>, Zero>>> Newtype for U {
+    type Inner = T;
+
+    #[inline]
+    fn from_inner(x: T) -> Self {
+        singleton(x)
+    }
+
+    #[inline]
+    fn into_inner(self) -> T {
+        match self.into_repr() {
+            Sum::This(Product(x, One)) => x,
+            Sum::Next(a) => match a {},
+        }
+    }
+
+    #[inline]
+    fn as_inner(&self) -> &T {
+        match self.as_repr() {
+            Sum::This(Product(x, One)) => x,
+            Sum::Next(a) => match a {},
+        }
+    }
+
+    #[inline]
+    fn as_inner_mut(&mut self) -> &mut T {
+        match self.as_mut_repr() {
+            Sum::This(Product(x, One)) => x,
+            Sum::Next(a) => match a {},
+        }
+    }
+}
+
+/// 为 [`Newtype`] 转发里面的类型的 trait
+///
+/// 支持 `Display`、`LowerHex`、`UpperHex`、`Binary`、`Octal`、`FromStr`、
+/// `From`（从里面的类型转换）、`Deref`、`DerefMut`、`AsRef`、`AsMut`。
+/// 仅支持没有泛型参数的类型。
+///
+/// ```
+/// # use algtype::{Generic, forward_newtype};
+/// #[derive(Generic, Debug, PartialEq)]
+/// struct UserId(u64);
+///
+/// forward_newtype!(UserId: Display, FromStr, From, Deref);
+///
+/// assert_eq!(UserId(42).to_string(), "42");
+/// assert_eq!("42".parse(), Ok(UserId(42)));
+/// assert_eq!(UserId::from(42), UserId(42));
+/// assert_eq!(UserId(42).pow(2), 1764);
+/// ```
+#[macro_export]
+macro_rules! forward_newtype {
+    (@fmt $tr:ident $ty:ty) => {
+        impl ::core::fmt::$tr for $ty {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::$tr::fmt($crate::utils::Newtype::as_inner(self), f)
+            }
+        }
+    };
+    (@Display $ty:ty) => {
+        $crate::forward_newtype!(@fmt Display $ty);
+    };
+    (@LowerHex $ty:ty) => {
+        $crate::forward_newtype!(@fmt LowerHex $ty);
+    };
+    (@UpperHex $ty:ty) => {
+        $crate::forward_newtype!(@fmt UpperHex $ty);
+    };
+    (@Binary $ty:ty) => {
+        $crate::forward_newtype!(@fmt Binary $ty);
+    };
+    (@Octal $ty:ty) => {
+        $crate::forward_newtype!(@fmt Octal $ty);
+    };
+    (@FromStr $ty:ty) => {
+        impl ::core::str::FromStr for $ty {
+            type Err = <<$ty as $crate::utils::Newtype>::Inner as ::core::str::FromStr>::Err;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                s.parse().map($crate::utils::Newtype::from_inner)
+            }
+        }
+    };
+    (@From $ty:ty) => {
+        impl ::core::convert::From<<$ty as $crate::utils::Newtype>::Inner> for $ty {
+            fn from(x: <$ty as $crate::utils::Newtype>::Inner) -> Self {
+                $crate::utils::Newtype::from_inner(x)
+            }
+        }
+    };
+    (@Deref $ty:ty) => {
+        impl ::core::ops::Deref for $ty {
+            type Target = <$ty as $crate::utils::Newtype>::Inner;
+
+            fn deref(&self) -> &Self::Target {
+                $crate::utils::Newtype::as_inner(self)
+            }
+        }
+    };
+    (@DerefMut $ty:ty) => {
+        impl ::core::ops::DerefMut for $ty {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                $crate::utils::Newtype::as_inner_mut(self)
+            }
+        }
+    };
+    (@AsRef $ty:ty) => {
+        impl ::core::convert::AsRef<<$ty as $crate::utils::Newtype>::Inner> for $ty {
+            fn as_ref(&self) -> &<$ty as $crate::utils::Newtype>::Inner {
+                $crate::utils::Newtype::as_inner(self)
+            }
+        }
+    };
+    (@AsMut $ty:ty) => {
+        impl ::core::convert::AsMut<<$ty as $crate::utils::Newtype>::Inner> for $ty {
+            fn as_mut(&mut self) -> &mut <$ty as $crate::utils::Newtype>::Inner {
+                $crate::utils::Newtype::as_inner_mut(self)
+            }
+        }
+    };
+    ($ty:ty: $($tr:ident),* $(,)?) => {
+        $($crate::forward_newtype!(@$tr $ty);)*
+    };
+}
+
 /// 转换表示一样的类型
 ///
 /// ```
@@ -26,6 +184,141 @@ pub fn cast<T: Generic, U: Generic<Repr = T::Repr>>(x: T) -> U {
     U::from_repr(x.into_repr())
 }
 
+/// 转换成引用形式
+///
+/// 引用形式是表示为 `Repr::Ref` 的类型，可由 `#[algtype(view = ...)]` 生成。
+///
+/// ```
+/// # use algtype::{Generic, utils::view};
+/// #[derive(Generic)]
+/// #[algtype(view = ShapeRef)]
+/// enum Shape {
+///     Circle(f64),
+///     Rect { w: f64, h: f64 },
+/// }
+///
+/// let s = Shape::Rect { w: 1.0, h: 2.0 };
+/// match view(&s) {
+///     ShapeRef::Circle(r) => assert_eq!(*r, 1.0),
+///     ShapeRef::Rect { w, h } => assert_eq!(w * h, 2.0),
+/// }
+/// ```
+pub fn view<'a, T: Generic, U: Generic<Repr = <T::Repr as Repr>::Ref<'a>>>(x: &'a T) -> U {
+    U::from_repr(x.as_repr())
+}
+
+/// 转换成可变引用形式
+///
+/// 可变引用形式是表示为 `Repr::Mut` 的类型，可由 `#[algtype(mut_view = ...)]` 生成。
+pub fn view_mut<'a, T: Generic, U: Generic<Repr = <T::Repr as Repr>::Mut<'a>>>(x: &'a mut T) -> U {
+    U::from_repr(x.as_mut_repr())
+}
+
+trait CastRepr<S>: Repr + Sized {
+    fn cast_repr(src: S) -> Self;
+}
+
+impl CastRepr<Zero> for Zero {
+    fn cast_repr(src: Zero) -> Self {
+        src
+    }
+}
+
+impl CastRepr<One> for One {
+    fn cast_repr(src: One) -> Self {
+        src
+    }
+}
+
+impl<T: CastRepr<T2>, R: CastRepr<R2>, T2, R2> CastRepr<Sum<T2, R2>> for Sum<T, R> {
+    fn cast_repr(src: Sum<T2, R2>) -> Self {
+        match src {
+            Sum::This(x) => Sum::This(T::cast_repr(x)),
+            Sum::Next(x) => Sum::Next(R::cast_repr(x)),
+        }
+    }
+}
+
+impl<T: From<T2>, R: CastRepr<R2>, T2, R2> CastRepr<Product<T2, R2>> for Product<T, R> {
+    fn cast_repr(src: Product<T2, R2>) -> Self {
+        Product(src.0.into(), R::cast_repr(src.1))
+    }
+}
+
+/// 转换结构一样的类型，其字段通过 [`From`] 转换
+///
+/// ```
+/// # use algtype::utils::cast_with;
+/// let a: (i64, f64) = cast_with([1i32, 2]);
+/// assert_eq!(a, (1, 2.0));
+/// ```
+#[allow(private_bounds)]
+pub fn cast_with<T: Generic, U: Generic>(x: T) -> U
+where
+    U::Repr: CastRepr<T::Repr>,
+{
+    U::from_repr(U::Repr::cast_repr(x.into_repr()))
+}
+
+/// `index` 是字段的位置
+trait TryCastRepr<S>: Repr + Sized {
+    fn try_cast_repr(src: S, index: usize) -> Result<Self, usize>;
+}
+
+impl TryCastRepr<Zero> for Zero {
+    fn try_cast_repr(src: Zero, _index: usize) -> Result<Self, usize> {
+        Ok(src)
+    }
+}
+
+impl TryCastRepr<One> for One {
+    fn try_cast_repr(src: One, _index: usize) -> Result<Self, usize> {
+        Ok(src)
+    }
+}
+
+impl<T: TryCastRepr<T2>, R: TryCastRepr<R2>, T2, R2> TryCastRepr<Sum<T2, R2>> for Sum<T, R> {
+    fn try_cast_repr(src: Sum<T2, R2>, _index: usize) -> Result<Self, usize> {
+        Ok(match src {
+            Sum::This(x) => Sum::This(T::try_cast_repr(x, 0)?),
+            Sum::Next(x) => Sum::Next(R::try_cast_repr(x, 0)?),
+        })
+    }
+}
+
+impl<T: TryFrom<T2>, R: TryCastRepr<R2>, T2, R2> TryCastRepr<Product<T2, R2>> for Product<T, R> {
+    fn try_cast_repr(src: Product<T2, R2>, index: usize) -> Result<Self, usize> {
+        let x = src.0.try_into().map_err(|_| index)?;
+        Ok(Product(x, R::try_cast_repr(src.1, index + 1)?))
+    }
+}
+
+/// 转换结构一样的类型，其字段通过 [`TryFrom`] 转换
+///
+/// 若失败，则返回第一个失败的字段的位置。
+///
+/// ```
+/// # use algtype::{Generic, utils::try_cast};
+/// # use core::num::NonZeroU32;
+/// #[derive(Generic)]
+/// struct Raw(u32, u32);
+///
+/// #[derive(Generic, Debug, PartialEq)]
+/// struct Checked(NonZeroU32, u16);
+///
+/// let ok = try_cast(Raw(1, 2));
+/// assert_eq!(ok, Ok(Checked(NonZeroU32::new(1).unwrap(), 2)));
+/// assert_eq!(try_cast::<_, Checked>(Raw(0, 2)), Err(0));
+/// assert_eq!(try_cast::<_, Checked>(Raw(1, 65536)), Err(1));
+/// ```
+#[allow(private_bounds)]
+pub fn try_cast<T: Generic, U: Generic>(x: T) -> Result<U, usize>
+where
+    U::Repr: TryCastRepr<T::Repr>,
+{
+    U::Repr::try_cast_repr(x.into_repr(), 0).map(U::from_repr)
+}
+
 trait VariantCount {
     const COUNT: u32;
 }
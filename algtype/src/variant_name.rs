@@ -0,0 +1,128 @@
+//! 无字段的枚举与字符串互相转换
+//!
+//! 若 enum 的各变体都没有字段，[`VariantName`] 能按 [`Generic::META`] 中的名字转换变体与字符串。
+//! 派生 [`GenericDisplay`](crate::GenericDisplay) 和 [`GenericFromStr`](crate::GenericFromStr)
+//! 以实现 `Display` 和 `FromStr`。名字可由 `#[algtype(rename_all = "kebab-case")]` 之类的属性改写，
+//! 支持 `lowercase`、`UPPERCASE`、`PascalCase`、`camelCase`、`snake_case`、`SCREAMING_SNAKE_CASE`
+//! 和 `kebab-case`。
+//!
+//! ```
+//! # use algtype::{Generic, GenericDisplay, GenericFromStr, variant_name::VariantName};
+//! #[derive(Generic, GenericDisplay, GenericFromStr, Debug, PartialEq)]
+//! #[algtype(rename_all = "kebab-case")]
+//! enum Level {
+//!     Debug,
+//!     WarnOnce,
+//! }
+//!
+//! assert_eq!(Level::WarnOnce.variant_name(), Some("warn-once"));
+//! assert_eq!(Level::from_variant_name("debug"), Some(Level::Debug));
+//! assert_eq!("warn-once".parse(), Ok(Level::WarnOnce));
+//! assert_eq!(Level::Debug.to_string(), "debug");
+//!
+//! let e = "info".parse::<Level>().unwrap_err();
+//! assert_eq!(e.to_string(), "unknown variant of Level, expected one of: debug, warn-once");
+//! ```
+
+use crate::{Generic, Meta, One, Repr, Sum, Zero};
+use core::fmt;
+
+/// 各变体都没有字段的和类型
+pub trait GUnit: Repr + Sized {
+    /// 变体的序号
+    fn g_index(this: Self::Ref<'_>) -> usize;
+    /// 按序号构造变体
+    fn g_from_index(index: usize) -> Option<Self>;
+}
+
+impl GUnit for Zero {
+    fn g_index(this: Self::Ref<'_>) -> usize {
+        match this {}
+    }
+
+    fn g_from_index(_: usize) -> Option<Self> {
+        None
+    }
+}
+
+impl<R: GUnit> GUnit for Sum<One, R> {
+    fn g_index(this: Self::Ref<'_>) -> usize {
+        match this {
+            Sum::This(One) => 0,
+            Sum::Next(r) => R::g_index(r) + 1,
+        }
+    }
+
+    fn g_from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(Sum::This(One)),
+            i => R::g_from_index(i - 1).map(Sum::Next),
+        }
+    }
+}
+
+/// 按名字转换无字段的变体
+///
+/// 名字取自 [`Generic::META`]，没有名字信息时两个方法都返回 `None`。
+/// 派生的 `Display` 和 `FromStr` 此时改用变体的序号。
+pub trait VariantName: Generic + Sized {
+    /// 变体的序号
+    fn variant_index(&self) -> usize;
+    /// 按序号构造变体
+    fn from_variant_index(index: usize) -> Option<Self>;
+
+    /// 变体的名字
+    fn variant_name(&self) -> Option<&'static str> {
+        Some(Self::META?.variants[self.variant_index()].name)
+    }
+
+    /// 按名字构造变体
+    fn from_variant_name(name: &str) -> Option<Self> {
+        let index = Self::META?.variants.iter().position(|v| v.name == name)?;
+        Self::from_variant_index(index)
+    }
+}
+
+impl<T: Generic> VariantName for T
+where
+    T::Repr: GUnit,
+{
+    fn variant_index(&self) -> usize {
+        T::Repr::g_index(self.as_repr())
+    }
+
+    fn from_variant_index(index: usize) -> Option<Self> {
+        T::Repr::g_from_index(index).map(T::from_repr)
+    }
+}
+
+/// 名字不对应任何变体
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseVariantError {
+    meta: Option<&'static Meta>,
+}
+
+impl ParseVariantError {
+    /// `T` 的解析错误
+    pub fn new<T: Generic>() -> Self {
+        Self { meta: T::META }
+    }
+}
+
+impl fmt::Display for ParseVariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(meta) = self.meta else {
+            return f.write_str("unknown variant");
+        };
+        write!(f, "unknown variant of {}, expected one of: ", meta.name)?;
+        for (i, v) in meta.variants.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(v.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ParseVariantError {}
@@ -0,0 +1,60 @@
+use algtype::{
+    approx_eq::{ApproxEq, GenericApproxEq, Tolerance},
+    assert_approx_eq, Generic,
+};
+
+#[derive(Generic, Debug)]
+enum Shape {
+    Circle { r: f32 },
+    Poly(Vec<(f64, f64)>),
+}
+
+impl GenericApproxEq for Shape {}
+
+#[derive(Generic, Debug)]
+struct State {
+    id: u32,
+    shapes: [Shape; 2],
+}
+
+impl GenericApproxEq for State {}
+
+#[test]
+fn test() {
+    let tol = Tolerance::default();
+    let state = |r: f32, y: f64| State {
+        id: 1,
+        shapes: [Shape::Circle { r }, Shape::Poly(vec![(0.0, 0.0), (1.0, y)])],
+    };
+    let diff = |a: &State, b: &State| a.approx_diff(b, &tol).map(|d| d.path().to_vec());
+
+    assert_approx_eq!(state(0.1 + 0.2, 1.0), state(0.3, 1.0 + 1e-9));
+    assert_eq!(
+        diff(&state(1.0, 1.0), &state(1.1, 1.0)),
+        Some(vec![1, 0, 0])
+    );
+    assert_eq!(
+        diff(&state(1.0, 1.0), &state(1.0, 1.1)),
+        Some(vec![1, 1, 0, 1, 1])
+    );
+
+    let mut other = state(1.0, 1.0);
+    other.shapes.swap(0, 1);
+    assert_eq!(diff(&state(1.0, 1.0), &other), Some(vec![1, 0]));
+    other.id = 2;
+    assert_eq!(diff(&state(1.0, 1.0), &other), Some(vec![0]));
+
+    assert!(!f64::NAN.approx_eq(&f64::NAN, &tol));
+    assert!((-0.0f32).approx_eq(&0.0, &Tolerance::new(0.0, 0)));
+    assert!(1e10f64.approx_eq(
+        &f64::from_bits(1e10f64.to_bits() + 1),
+        &Tolerance::new(0.0, 1)
+    ));
+    assert!(!1e10f64.approx_eq(&(1e10 + 1.0), &Tolerance::new(0.0, 4)));
+
+    let msg = std::panic::catch_unwind(|| assert_approx_eq!((1u8, 2.0), (1u8, 2.5)))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+    assert!(msg.starts_with("assertion `left ≈ right` failed: differ at 1\n"));
+}
@@ -0,0 +1,63 @@
+use algtype::{
+    builder::{Builder, Optional, Set, Unset},
+    index::*,
+    Generic, One, Product,
+};
+
+#[derive(Generic, Debug, PartialEq)]
+struct Request<'a> {
+    method: &'a str,
+    path: String,
+    retries: u8,
+    headers: Vec<(&'a str, &'a str)>,
+}
+
+#[derive(Generic, Debug, PartialEq)]
+struct Unit;
+
+#[test]
+fn test() {
+    let base = Builder::<Request>::new()
+        .set::<I0>("GET")
+        .set_default::<I2>()
+        .set_default::<I3>();
+    let _: &Builder<Request, Product<Set<&str>, Product<Unset, _>>> = &base;
+
+    let r = base.set::<I1>("/".into()).set::<I2>(3).build();
+    assert_eq!(
+        r,
+        Request {
+            method: "GET",
+            path: "/".into(),
+            retries: 3,
+            headers: vec![],
+        }
+    );
+
+    // 可选的字段取默认值，仍可以覆盖
+    let opt = Builder::<Request>::new()
+        .optional::<I0>()
+        .optional::<I2>()
+        .optional::<I3>();
+    let _: &Builder<Request, Product<Optional, Product<Unset, _>>> = &opt;
+    let r = opt.set::<I1>("/".into()).set::<I2>(5).build();
+    assert_eq!(
+        r,
+        Request {
+            method: "",
+            path: "/".into(),
+            retries: 5,
+            headers: vec![],
+        }
+    );
+
+    let _: Builder<Unit, One> = Builder::default();
+    assert_eq!(Builder::<Unit>::new().build(), Unit);
+    assert_eq!(
+        Builder::<(u8, char)>::new()
+            .set::<I1>('a')
+            .set::<I0>(1)
+            .build(),
+        (1, 'a')
+    );
+}
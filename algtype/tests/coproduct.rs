@@ -0,0 +1,44 @@
+use algtype::{
+    coproduct::{HasVariant, Inject},
+    inject_from, Generic,
+};
+
+#[derive(Debug, PartialEq)]
+struct Click(i32, i32);
+
+#[derive(Generic, Debug, PartialEq)]
+enum Event {
+    Quit,
+    Key(char),
+    Click(Click),
+    Scroll { delta: f32 },
+}
+
+inject_from!(Event: char, Click, f32);
+
+fn count<E: HasVariant<char, I>, I>(events: &[E]) -> usize {
+    events.iter().filter_map(|e| e.payload()).count()
+}
+
+#[test]
+fn test() {
+    assert_eq!(Event::inject('a'), Event::Key('a'));
+    assert_eq!(Event::from(Click(1, 2)), Event::Click(Click(1, 2)));
+    assert_eq!(Event::from(1.5), Event::Scroll { delta: 1.5 });
+
+    assert_eq!(Event::Key('a').extract::<char, _>(), Ok('a'));
+    assert_eq!(Event::Quit.extract::<char, _>(), Err(Event::Quit));
+    assert_eq!(
+        Event::Click(Click(0, 0)).extract::<f32, _>(),
+        Err(Event::Click(Click(0, 0)))
+    );
+    assert_eq!(
+        Event::Click(Click(3, 4)).extract_ref::<Click, _>(),
+        Some(&Click(3, 4))
+    );
+    assert_eq!(count(&[Event::Quit, 'x'.into(), 'y'.into()]), 2);
+
+    assert_eq!(Option::inject(1u8), Some(1));
+    assert_eq!(Result::<u8, char>::inject('e'), Err('e'));
+    assert_eq!(Ok::<u8, char>(1).extract::<char, _>(), Err(Ok(1)));
+}
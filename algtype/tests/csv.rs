@@ -0,0 +1,136 @@
+use algtype::{
+    csv::{Dialect, ErrorKind, GenericRecord, Record},
+    Generic, GenericDisplay, GenericFromStr,
+};
+
+#[derive(Generic, Debug, PartialEq)]
+struct Row {
+    id: u32,
+    name: String,
+    score: f64,
+    level: Level,
+}
+
+#[derive(Generic, GenericDisplay, GenericFromStr, Debug, PartialEq)]
+#[algtype(rename_all = "lowercase")]
+enum Level {
+    Low,
+    High,
+}
+
+impl GenericRecord for Row {}
+
+#[test]
+fn test() {
+    let rows = [
+        Row {
+            id: 1,
+            name: "plain".into(),
+            score: 0.5,
+            level: Level::Low,
+        },
+        Row {
+            id: 2,
+            name: "a, \"b\"\nc".into(),
+            score: -1.0,
+            level: Level::High,
+        },
+    ];
+    let mut out = String::new();
+    Dialect::CSV.write_header::<Row>(&mut out);
+    rows.iter().for_each(|r| Dialect::CSV.write(&mut out, r));
+    let text = "id,name,score,level\n1,plain,0.5,low\n2,\"a, \"\"b\"\"\nc\",-1,high\n";
+    assert_eq!(out, text);
+    let read: Result<Vec<Row>, _> = Dialect::CSV.reader_with_header(text).unwrap().collect();
+    assert_eq!(read.unwrap(), rows);
+
+    // CRLF、空行与末尾没有换行
+    let text = "1\tx\t2\tlow\r\n\r\n3\t\"\"\t4\thigh";
+    let read: Vec<Row> = Dialect::TSV.reader(text).map(Result::unwrap).collect();
+    assert_eq!((read[0].name.as_str(), read[1].name.as_str()), ("x", ""));
+    assert_eq!(read[1].level, Level::High);
+
+    // 各种错误及其位置
+    let e = Dialect::CSV
+        .reader_with_header::<Row>("id,name,level,score\n")
+        .err()
+        .unwrap();
+    assert_eq!((e.line(), e.column()), (1, Some(3)));
+    assert_eq!(
+        e.to_string(),
+        "line 1, column 3: expected header `score`, found `level`"
+    );
+
+    let text = "1,a,0,low\n2,\"b\nb\",0\n3,c,x,low\n4,\"d\"d,0,low\n5,e,0,mid\n6,\"f";
+    let errs: Vec<_> = Dialect::CSV.reader::<Row>(text).collect();
+    assert!(errs[0].is_ok());
+    let e = errs[1].as_ref().unwrap_err();
+    assert_eq!(
+        e.kind(),
+        &ErrorKind::Length {
+            expected: 4,
+            found: 3
+        }
+    );
+    assert_eq!(e.to_string(), "line 2: expected 4 columns, found 3");
+    let e = errs[2].as_ref().unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "line 4, column 3 (score): invalid float literal"
+    );
+    let e = errs[3].as_ref().unwrap_err();
+    assert_eq!(e.to_string(), "line 5, column 2: malformed quoted cell");
+    let e = errs[4].as_ref().unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "line 6, column 4 (level): unknown variant of Level, expected one of: low, high"
+    );
+    let e = errs[5].as_ref().unwrap_err();
+    assert_eq!((e.line(), e.column()), (7, Some(2)));
+    assert_eq!(errs.len(), 6);
+
+    // 元组没有名字，表头为序号
+    assert_eq!(<(u8, char)>::column_name(1), None);
+    let mut out = String::new();
+    Dialect::CSV.write_header::<(u8, char)>(&mut out);
+    Dialect::CSV.write(&mut out, &(7, ','));
+    assert_eq!(out, "0,1\n7,\",\"\n");
+    let read: Vec<(u8, char)> = Dialect::CSV
+        .reader_with_header(&out)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(read, [(7, ',')]);
+
+    // 仅有一列时，空的格子不是空行
+    #[derive(Generic, Debug, PartialEq)]
+    struct Name {
+        name: String,
+    }
+    impl GenericRecord for Name {}
+
+    let names = ["a", "", "b"].map(|name| Name { name: name.into() });
+    let mut out = String::new();
+    Dialect::CSV.write_header::<Name>(&mut out);
+    names.iter().for_each(|r| Dialect::CSV.write(&mut out, r));
+    assert_eq!(out, "name\na\n\"\"\nb\n");
+    let read: Vec<Name> = Dialect::CSV
+        .reader_with_header(&out)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(read, names);
+    let read: Vec<Name> = Dialect::CSV
+        .reader("a\r\n\r\nb")
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(read, names);
+
+    // 没有列时，每行都是空的
+    let mut out = String::new();
+    Dialect::CSV.write(&mut out, &());
+    Dialect::CSV.write(&mut out, &());
+    assert_eq!(out, "\n\n");
+    let read: Vec<()> = Dialect::CSV.reader(&out).map(Result::unwrap).collect();
+    assert_eq!(read, [(), ()]);
+}
@@ -0,0 +1,67 @@
+use algtype::{
+    data::{self, Data, GenericData},
+    Generic,
+};
+
+#[derive(Generic, Debug, Clone, Copy, PartialEq)]
+struct UserId(u64);
+
+#[derive(Generic, Debug, PartialEq)]
+enum Body {
+    Text(String),
+    Mention(UserId, Option<Box<Body>>),
+}
+
+#[derive(Generic, Debug, PartialEq)]
+struct Message {
+    from: UserId,
+    to: Vec<UserId>,
+    body: [Body; 2],
+}
+
+impl GenericData for UserId {}
+impl Data for Body {
+    fn gmap(&self, f: &mut dyn FnMut(&dyn Data)) {
+        data::generic_gmap(self, f)
+    }
+    fn gmap_mut(&mut self, f: &mut dyn FnMut(&mut dyn Data)) {
+        data::generic_gmap_mut(self, f)
+    }
+}
+impl GenericData for Message {}
+
+#[test]
+fn test() {
+    let mut msg = Message {
+        from: UserId(1),
+        to: vec![UserId(2), UserId(3)],
+        body: [
+            Body::Text("hi".into()),
+            Body::Mention(UserId(4), Some(Box::new(Body::Text("x".into())))),
+        ],
+    };
+
+    let ids = msg.everything(vec![], |mut v, id: &UserId| {
+        v.push(id.0);
+        v
+    });
+    assert_eq!(ids, [1, 2, 3, 4]);
+    // UserId 内部的 u64 也会被访问
+    assert_eq!(msg.everything(0, |n, _: &u64| n + 1), 4);
+
+    msg.everywhere_mut(|id: &mut UserId| id.0 *= 10);
+    msg.everywhere_mut(|s: &mut String| s.push('!'));
+    assert_eq!(msg.to, [UserId(20), UserId(30)]);
+    assert_eq!(
+        msg.body[1],
+        Body::Mention(UserId(40), Some(Box::new(Body::Text("x!".into()))))
+    );
+
+    // 先内层后外层
+    let mut order = vec![];
+    let mut x = Some(Some(1u8));
+    x.everywhere_mut(|y: &mut Option<u8>| order.push(y.is_some()));
+    x.everywhere_mut(|y: &mut u8| *y += 1);
+    assert_eq!(x, Some(Some(2)));
+    assert_eq!(order, [true]);
+}
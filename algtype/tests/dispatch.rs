@@ -0,0 +1,80 @@
+use algtype::{coerce_dyn, dispatch::Dispatch, Generic};
+use core::fmt::{Debug, Display};
+
+trait Shape {
+    fn area(&self) -> u32;
+    fn grow(&mut self, by: u32);
+}
+
+struct Square(u32);
+
+struct Rect {
+    w: u32,
+    h: u32,
+}
+
+struct Borrowed<'a>(&'a mut u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+
+    fn grow(&mut self, by: u32) {
+        self.0 += by;
+    }
+}
+
+impl Shape for Rect {
+    fn area(&self) -> u32 {
+        self.w * self.h
+    }
+
+    fn grow(&mut self, by: u32) {
+        self.w += by;
+        self.h += by;
+    }
+}
+
+impl Shape for Borrowed<'_> {
+    fn area(&self) -> u32 {
+        *self.0
+    }
+
+    fn grow(&mut self, by: u32) {
+        *self.0 += by;
+    }
+}
+
+coerce_dyn!(Shape);
+
+#[derive(Generic)]
+enum Shapes<'a> {
+    Square(Square),
+    Rect { rect: Rect },
+    Borrowed(Borrowed<'a>),
+}
+
+#[test]
+fn test() {
+    let mut s = Shapes::Rect {
+        rect: Rect { w: 2, h: 3 },
+    };
+    assert_eq!(s.as_dyn::<dyn Shape>().area(), 6);
+    s.as_dyn_mut::<dyn Shape>().grow(1);
+    assert_eq!(s.as_dyn::<dyn Shape>().area(), 12);
+    assert_eq!(Shapes::Square(Square(3)).as_dyn::<dyn Shape>().area(), 9);
+
+    let mut n = 5;
+    let mut s = Shapes::Borrowed(Borrowed(&mut n));
+    s.as_dyn_mut::<dyn Shape + '_>().grow(2);
+    assert_eq!(s.as_dyn::<dyn Shape + '_>().area(), 7);
+    assert_eq!(n, 7);
+
+    let r: Result<u8, &str> = Err("oops");
+    assert_eq!(r.as_dyn::<dyn Display>().to_string(), "oops");
+    assert_eq!(
+        format!("{:?}", Ok::<_, &str>(1u8).as_dyn::<dyn Debug>()),
+        "1"
+    );
+}
@@ -0,0 +1,87 @@
+use algtype::{
+    dynamic::{DynValue, Dynamic, GenericDynamic, Key},
+    Generic,
+};
+use std::fmt::Debug;
+
+#[derive(Clone, Generic, Debug, PartialEq)]
+struct Config {
+    name: String,
+    size: (u8, i64),
+    ratio: Option<f32>,
+    tags: Vec<Tag>,
+}
+
+#[derive(Clone, Generic, Debug, PartialEq)]
+enum Tag {
+    Unit,
+    Tuple(char, bool),
+    Struct { r#type: u16 },
+}
+
+impl GenericDynamic for Config {}
+impl GenericDynamic for Tag {}
+
+fn assert_dyn<T: Dynamic + PartialEq + Debug + Clone>(x: T) {
+    assert_eq!(T::from_dyn(x.to_dyn()), Some(x));
+}
+
+fn name(s: &'static str) -> Key {
+    Key::Name(s.into())
+}
+
+#[test]
+fn test() {
+    let config = Config {
+        name: "a".into(),
+        size: (1, -2),
+        ratio: Some(0.5),
+        tags: vec![Tag::Unit, Tag::Tuple('x', true), Tag::Struct { r#type: 3 }],
+    };
+    assert_dyn(config.clone());
+    assert_dyn([Tag::Unit, Tag::Tuple('y', false)]);
+    assert_dyn(Ok::<_, ()>(Tag::Struct { r#type: 0 }));
+
+    assert_eq!(
+        Tag::Struct { r#type: 3 }.to_dyn(),
+        DynValue::Variant {
+            key: name("Struct"),
+            fields: vec![(name("type"), DynValue::UInt(3))],
+        }
+    );
+    assert_eq!(
+        Tag::Tuple('x', true).to_dyn(),
+        DynValue::Variant {
+            key: name("Tuple"),
+            fields: vec![
+                (Key::Index(0), DynValue::Str("x".into())),
+                (Key::Index(1), DynValue::Bool(true)),
+            ],
+        }
+    );
+
+    // 有名字信息者，也可以序号为键
+    let v = DynValue::Variant {
+        key: Key::Index(2),
+        fields: vec![(Key::Index(0), DynValue::Int(7))],
+    };
+    assert_eq!(Tag::from_dyn(v), Some(Tag::Struct { r#type: 7 }));
+
+    // 字段多余、缺少或类型不符
+    let fields = |n| match config.to_dyn() {
+        DynValue::Struct { mut fields } => {
+            fields.truncate(n);
+            fields
+        }
+        _ => unreachable!(),
+    };
+    let mut more = fields(4);
+    more.push((name("extra"), DynValue::Unit));
+    assert_eq!(Config::from_dyn(DynValue::Struct { fields: more }), None);
+    assert_eq!(
+        Config::from_dyn(DynValue::Struct { fields: fields(3) }),
+        None
+    );
+    assert_eq!(u8::from_dyn(DynValue::Int(256)), None);
+    assert_eq!(Tag::from_dyn(DynValue::Struct { fields: vec![] }), None);
+}
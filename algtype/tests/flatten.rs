@@ -0,0 +1,85 @@
+use algtype::{stable_hash::*, FieldsMeta, Generic, One, Product, Sum, Zero};
+
+#[derive(Generic, Debug, Clone, PartialEq)]
+enum Inner {
+    A,
+    B(bool),
+}
+
+#[derive(Generic, Debug, Clone, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Generic, Debug, Clone, PartialEq)]
+enum Outer<T> {
+    Inner(#[algtype(flatten)] Inner),
+    C {
+        t: T,
+        #[algtype(flatten)]
+        p: Point,
+    },
+    D,
+}
+
+#[derive(Generic, Debug, Clone, PartialEq)]
+enum Flat<T> {
+    A,
+    B(bool),
+    C(T, i32, i32),
+    D,
+}
+
+// 展开的类型用到泛型参数
+#[derive(Generic)]
+struct Wrap<T> {
+    #[algtype(flatten)]
+    flat: Flat<T>,
+}
+
+impl<T: StableHash> GenericStableHash for Outer<T> {}
+impl<T: StableHash> GenericStableHash for Flat<T> {}
+
+type FlatRepr = Sum<
+    One,
+    Sum<Product<bool, One>, Sum<Product<u8, Product<i32, Product<i32, One>>>, Sum<One, Zero>>>,
+>;
+
+#[test]
+fn test() {
+    let _: FlatRepr = Outer::<u8>::D.into_repr();
+    let meta = Outer::<Flat<u8>>::META.unwrap();
+    assert_eq!((meta.name, meta.is_enum), ("Outer", true));
+    let names: Vec<_> = meta.variants.iter().map(|v| v.name).collect();
+    assert_eq!(names, ["A", "B", "C", "D"]);
+    assert_eq!(meta.variants[1].fields, FieldsMeta::Unnamed);
+    assert_eq!(meta.variants[2].fields, FieldsMeta::Named(&["t", "x", "y"]));
+    assert_eq!(meta.variants[3].fields, FieldsMeta::Unit);
+    assert_eq!(Wrap::<u8>::META, None);
+
+    let pairs = [
+        (Outer::Inner(Inner::A), Flat::A),
+        (Outer::Inner(Inner::B(true)), Flat::B(true)),
+        (
+            Outer::C {
+                t: 1u8,
+                p: Point { x: 2, y: 3 },
+            },
+            Flat::C(1, 2, 3),
+        ),
+        (Outer::D, Flat::D),
+    ];
+    for (mut o, f) in pairs {
+        assert_eq!(stable_hash(&o), stable_hash(&f));
+        assert_eq!(algtype::utils::cast::<_, Flat<u8>>(o.clone()), f);
+        assert_eq!(algtype::utils::cast::<_, Outer<u8>>(f.clone()), o);
+        if let Sum::Next(Sum::Next(Sum::This(Product(t, Product(x, _))))) = o.as_mut_repr() {
+            *t += 1;
+            *x += 1;
+        }
+        if let Outer::C { t, p } = &o {
+            assert_eq!((*t, p.x), (2, 3));
+        }
+    }
+}
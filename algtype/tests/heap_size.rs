@@ -0,0 +1,75 @@
+use algtype::{
+    heap_size::{self, total_size, GenericHeapSize, HeapSize},
+    Generic,
+};
+use std::{collections::BTreeMap, mem::size_of};
+
+#[derive(Generic)]
+enum Node {
+    Leaf(u64),
+    Pair(Box<[u64; 2]>, Box<Node2>),
+}
+
+#[derive(Generic)]
+struct Node2(Option<Box<(u8, String)>>);
+
+impl GenericHeapSize for Node {}
+impl GenericHeapSize for Node2 {}
+
+#[derive(Generic)]
+struct Cache {
+    name: String,
+    items: BTreeMap<u32, Vec<u8>>,
+    node: Option<Node>,
+}
+
+impl GenericHeapSize for Cache {}
+
+// 递归的类型
+#[derive(Generic)]
+struct List {
+    name: String,
+    next: Option<Box<List>>,
+}
+
+impl HeapSize for List {
+    fn heap_size(&self) -> usize {
+        heap_size::generic_heap_size(self)
+    }
+}
+
+#[test]
+fn test() {
+    let node = Node::Pair(
+        Box::new([1, 2]),
+        Box::new(Node2(Some(Box::new((1, "abc".into()))))),
+    );
+    let node_size = 16 + size_of::<Node2>() + size_of::<(u8, String)>() + 3;
+    assert_eq!(Node::Leaf(1).heap_size(), 0);
+    assert_eq!(node.heap_size(), node_size);
+
+    let mut items = BTreeMap::new();
+    items.insert(1, Vec::with_capacity(10));
+    let cache = Cache {
+        name: String::with_capacity(5),
+        items,
+        node: Some(node),
+    };
+    assert_eq!(
+        cache.heap_size(),
+        5 + size_of::<(u32, Vec<u8>)>() + 10 + node_size
+    );
+    assert_eq!(total_size(&cache), size_of::<Cache>() + cache.heap_size());
+    assert_eq!(total_size(&[1u16, 2, 3][..]), 6);
+
+    let list = List {
+        name: "ab".into(),
+        next: Some(Box::new(List {
+            name: "cde".into(),
+            next: None,
+        })),
+    };
+    assert_eq!(list.heap_size(), 2 + size_of::<List>() + 3);
+    let boxed: (Box<str>, Box<[u64]>) = ("ab".into(), vec![1, 2].into());
+    assert_eq!(boxed.heap_size(), 2 + 16);
+}
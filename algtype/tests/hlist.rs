@@ -0,0 +1,63 @@
+use algtype::{
+    hlist::{from_tuple, HList, Poly},
+    index::*,
+    One, Product,
+};
+
+struct Double;
+
+impl Poly<u8> for Double {
+    type Output = u16;
+    fn call(&mut self, x: u8) -> u16 {
+        x as u16 * 2
+    }
+}
+
+impl Poly<&str> for Double {
+    type Output = String;
+    fn call(&mut self, x: &str) -> String {
+        x.repeat(2)
+    }
+}
+
+struct Count(usize);
+
+impl<X> Poly<X> for Count {
+    type Output = usize;
+    fn call(&mut self, _x: X) -> usize {
+        self.0 += 1;
+        self.0
+    }
+}
+
+#[test]
+fn test() {
+    assert_eq!(One.len(), 0);
+    assert!(One.is_empty());
+    assert_eq!(<Product<u8, Product<char, One>> as HList>::LEN, 2);
+
+    let l = from_tuple((1u8, "ab", 2u8));
+    assert_eq!(
+        l.map(Double).into_tuple::<(_, _, _)>(),
+        (2, "abab".into(), 4)
+    );
+    assert_eq!(l.map(Count(0)).into_tuple::<(_, _, _)>(), (1, 2, 3));
+    assert_eq!(l.reverse(), from_tuple((2u8, "ab", 1u8)));
+    assert_eq!(One.reverse(), One);
+
+    let (a, b) = l.split_at::<I1>();
+    assert_eq!(a, Product(1, One));
+    assert_eq!(a.append(b), l);
+    let (a, b) = l.split_at::<I3>();
+    assert_eq!((a, b), (l, One));
+    let (a, b) = l.split_at::<I0>();
+    assert_eq!((a, b), (One, l));
+
+    let z = l.zip(from_tuple(['x', 'y', 'z']));
+    assert_eq!(
+        z.into_tuple::<(_, _, _)>(),
+        ((1, 'x'), ("ab", 'y'), (2, 'z'))
+    );
+    let arr: [u8; 2] = from_tuple((3, 4)).into_tuple();
+    assert_eq!(arr, [3, 4]);
+}
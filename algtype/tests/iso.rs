@@ -0,0 +1,46 @@
+use algtype::{iso::*, Generic, One, Product, Sum};
+
+#[derive(Generic, Debug, PartialEq)]
+enum Old {
+    A(u8),
+    B,
+    C { flag: bool },
+}
+
+#[derive(Generic, Debug, PartialEq)]
+enum New {
+    C { flag: bool },
+    A(u8),
+    B,
+}
+
+#[test]
+fn test() {
+    let iso = OnNext(SumSwap).then(SumSwap);
+    for (old, new) in [
+        (Old::A(1), New::A(1)),
+        (Old::B, New::B),
+        (Old::C { flag: true }, New::C { flag: true }),
+    ] {
+        assert_eq!(convert::<Old, New>(old, &iso), new);
+    }
+    assert_eq!(convert::<New, Old>(New::B, &iso.inverse()), Old::B);
+
+    type Fields = Product<u8, Sum<bool, char>>;
+    let x: Fields = Product(1, Sum::Next('a'));
+    let y = Distrib.to(x);
+    assert_eq!(y, Sum::Next(Product(1, 'a')));
+    assert_eq!(Distrib.from(y), x);
+
+    let assoc = Inverse(ProductAssoc)
+        .then(OnHead(ProductComm))
+        .then(ProductAssoc);
+    let x = Product(1u8, Product('a', One));
+    assert_eq!(assoc.to(x), Product('a', Product(1u8, One)));
+    assert_eq!(assoc.from(assoc.to(x)), x);
+
+    let gen = Gen::<Option<u8>>::new().then(SumSwap);
+    assert_eq!(gen.to(Some(1)), Sum::This(Product(1, One)));
+    assert_eq!(gen.from(Sum::Next(Sum::This(One))), None);
+    assert_eq!(Refl.then(ProductUnit).to(Product(1, One)), 1);
+}
@@ -0,0 +1,118 @@
+use algtype::{
+    json::{self, ErrorKind, GenericJson, Json, JsonValue, Segment},
+    Generic,
+};
+
+#[derive(Generic, Debug, PartialEq)]
+struct Config {
+    name: String,
+    size: (u8, i64),
+    ratio: Option<f32>,
+    tags: Vec<Tag>,
+}
+
+#[derive(Generic, Debug, PartialEq)]
+#[algtype(rename_all = "snake_case")]
+enum Tag {
+    Unit,
+    Tuple(char, bool),
+    Struct { r#type: u128 },
+}
+
+#[derive(Generic, Debug, PartialEq)]
+struct Point(i32, i32);
+
+#[derive(Generic, Debug, PartialEq)]
+struct Empty;
+
+impl GenericJson for Config {}
+impl GenericJson for Tag {}
+impl GenericJson for Point {}
+impl GenericJson for Empty {}
+
+#[test]
+fn test() {
+    let c = Config {
+        name: "a\"\n\u{1}é😀".into(),
+        size: (255, -1),
+        ratio: Some(0.5),
+        tags: vec![
+            Tag::Unit,
+            Tag::Tuple('x', true),
+            Tag::Struct { r#type: u128::MAX },
+        ],
+    };
+    let s = concat!(
+        r#"{"name":"a\"\n\u0001é😀","size":[255,-1],"ratio":{"Some":[0.5]},"#,
+        r#""tags":["unit",{"tuple":["x",true]},{"struct":{"type":340282366920938463463374607431768211455}}]}"#,
+    );
+    assert_eq!(json::to_string(&c), s);
+    assert_eq!(json::from_str::<Config>(s).unwrap(), c);
+
+    // 键的顺序和空白不影响解码
+    let s = r#" { "tags" : [ ] , "ratio" : "None", "size": [0, 1e0], "name": "😀\/" } "#;
+    let e = json::from_str::<Config>(s).unwrap_err();
+    assert_eq!(e.to_string(), "expected integer, found number at $.size[1]");
+    let s = s.replace("1e0", "-0");
+    let c = json::from_str::<Config>(&s).unwrap();
+    assert_eq!((c.name.as_str(), c.size, c.ratio), ("😀/", (0, 0), None));
+
+    assert_eq!(json::to_string(&Point(1, -2)), "[1,-2]");
+    assert_eq!(json::to_string(&Empty), "null");
+    assert_eq!(json::to_string(&[f64::NAN, 1e300]), "[null,1e300]");
+    assert_eq!(json::to_string(&Ok::<(), u8>(())), r#"{"Ok":[null]}"#);
+    assert_eq!(Empty::from_json_value(&JsonValue::Null), Ok(Empty));
+
+    let err = |s: &str| json::from_str::<Config>(s).unwrap_err();
+    let base = r#""name":"","size":[0,0],"ratio":"None""#;
+    let e = err(&format!(r#"{{{base},"tags":[{{"struct":{{}}}}]}}"#));
+    assert_eq!(e.kind(), &ErrorKind::MissingField("type"));
+    assert_eq!(
+        e.path().copied().collect::<Vec<_>>(),
+        [
+            Segment::Name("tags"),
+            Segment::Index(0),
+            Segment::Name("struct")
+        ]
+    );
+    assert_eq!(e.to_string(), "missing field `type` at $.tags[0].struct");
+    let e = err(&format!(r#"{{{base},"tags":[],"extra":1}}"#));
+    assert_eq!(e.to_string(), "unknown field `extra` at $");
+    let e = err(&format!(r#"{{{base},"tags":["Unit"]}}"#));
+    assert_eq!(e.to_string(), "unknown variant `Unit` at $.tags[0]");
+    let e = err(&format!(r#"{{{base},"tags":["tuple"]}}"#));
+    assert_eq!(
+        e.to_string(),
+        "expected object with one key, found string at $.tags[0]"
+    );
+    let e = err(&format!(r#"{{{base},"tags":[{{"tuple":{{}}}}]}}"#));
+    assert_eq!(
+        e.to_string(),
+        "expected array, found object at $.tags[0].tuple"
+    );
+    let e = err(&format!(r#"{{{base},"tags":[{{"tuple":["xy",true]}}]}}"#));
+    assert_eq!(
+        e.to_string(),
+        "expected string of one character, found string at $.tags[0].tuple[0]"
+    );
+    let e = err(r#"{"name":"","size":[0],"ratio":"None","tags":[]}"#);
+    assert_eq!(
+        e.kind(),
+        &ErrorKind::Length {
+            expected: 2,
+            found: 1
+        }
+    );
+    let e = err(r#"{"name":"","size":[256,0],"ratio":"None","tags":[]}"#);
+    assert_eq!(e.to_string(), "number out of range at $.size[0]");
+
+    let syntax = |s: &str| json::from_str::<JsonValue>(s).unwrap_err().to_string();
+    assert_eq!(syntax(r#"{"a" 1}"#), "expected `:` at offset 5 at $");
+    assert_eq!(syntax("[1,]"), "expected value at offset 3 at $");
+    assert_eq!(syntax("01"), "expected end of input at offset 1 at $");
+    assert_eq!(
+        syntax(r#""\ud800""#),
+        "expected low surrogate at offset 7 at $"
+    );
+    assert!(json::from_str::<JsonValue>(&"[".repeat(1000)).is_err());
+}
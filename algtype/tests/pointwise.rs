@@ -0,0 +1,84 @@
+use algtype::{
+    hlist::{self, HList, Poly},
+    pointwise::{scale, MapFields, SubFn, ZipFields},
+    Generic, GenericAdd, GenericMul, GenericNeg, GenericSub,
+};
+use core::time::Duration;
+
+#[derive(
+    Generic, GenericAdd, GenericSub, GenericMul, GenericNeg, Clone, Copy, Debug, PartialEq,
+)]
+struct Vec2 {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Generic, GenericAdd, GenericSub, Clone, Copy, Debug, PartialEq)]
+struct Stats {
+    count: u64,
+    elapsed: Duration,
+    pos: Vec2,
+}
+
+#[derive(Generic, GenericAdd, Debug, PartialEq)]
+struct Wrap<T>(T)
+where
+    T: Copy;
+
+struct Max;
+
+impl<X: Ord> Poly<(X, X)> for Max {
+    type Output = X;
+
+    fn call(&mut self, (x, y): (X, X)) -> X {
+        x.max(y)
+    }
+}
+
+struct Halve;
+
+impl Poly<i32> for Halve {
+    type Output = i32;
+
+    fn call(&mut self, x: i32) -> i32 {
+        x / 2
+    }
+}
+
+#[test]
+fn test() {
+    let a = Vec2 { x: 1, y: -2 };
+    let b = Vec2 { x: 3, y: 4 };
+    assert_eq!(a + b, Vec2 { x: 4, y: 2 });
+    assert_eq!(a - b, Vec2 { x: -2, y: -6 });
+    assert_eq!(-a, Vec2 { x: -1, y: 2 });
+    assert_eq!(a * b, Vec2 { x: 3, y: -8 });
+    assert_eq!(scale(b, 3), Vec2 { x: 9, y: 12 });
+    assert_eq!(a.zip_fields(b, Max), Vec2 { x: 3, y: 4 });
+    assert_eq!(b.map_fields(Halve), Vec2 { x: 1, y: 2 });
+
+    let mut c = a;
+    c += b;
+    c -= a;
+    c *= b;
+    assert_eq!(c, b * b);
+
+    // 字段可以是别的类型，包括实现了运算的 struct
+    let s = Stats {
+        count: 2,
+        elapsed: Duration::from_secs(3),
+        pos: a,
+    };
+    let mut t = s + s;
+    assert_eq!(t.count, 4);
+    assert_eq!(t.elapsed, Duration::from_secs(6));
+    assert_eq!(t.pos, a + a);
+    t -= s;
+    assert_eq!(t, s);
+
+    assert_eq!(Wrap(1u8) + Wrap(2), Wrap(3));
+    assert_eq!((5, 2.5).zip_fields((3, 0.5), SubFn), (2, 2.0));
+
+    let l = hlist::from_tuple((1, 'b')).zip_with(hlist::from_tuple((2, 'a')), Max);
+    assert_eq!(l.into_tuple::<(_, _)>(), (2, 'b'));
+}
@@ -0,0 +1,46 @@
+use algtype::{index::*, prism::Prism, select::GetByType, Generic, One, Product};
+
+#[derive(Generic, Debug, PartialEq)]
+enum Msg {
+    Ping,
+    Data(u8, String),
+    Nested { inner: Option<Inner> },
+}
+
+#[derive(Generic, Debug, PartialEq)]
+struct Inner {
+    id: u32,
+}
+
+#[test]
+fn test() {
+    assert!(Msg::Ping.is_variant::<I0>());
+    assert!(!Msg::Ping.is_variant::<I2>());
+
+    let mut m = Msg::Data(1, "a".into());
+    assert_eq!(
+        m.as_variant::<I1>(),
+        Some(Product(&1, Product(&"a".into(), One)))
+    );
+    if let Some(Product(n, Product(s, One))) = m.as_variant_mut::<I1>() {
+        *n += 1;
+        s.push('b');
+    }
+    assert_eq!(m, Msg::Data(2, "ab".into()));
+    let m = m.into_variant::<I0>().unwrap_err();
+    assert_eq!(
+        m.into_variant::<I1>(),
+        Ok(Product(2, Product("ab".into(), One)))
+    );
+
+    // 嵌套的载荷：Nested 的字段是 Option，其 Some 变体的字段是 Inner
+    let m = Msg::from_variant::<I2>(Product(Some(Inner { id: 7 }), One));
+    let id = m
+        .as_variant::<I2>()
+        .and_then(|Product(inner, One)| inner.as_variant::<I1>())
+        .map(|Product(inner, One)| *inner.get_by_type::<u32, _>());
+    assert_eq!(id, Some(7));
+    assert_eq!(Some(1).into_variant::<I1>(), Ok(Product(1, One)));
+    // Result 的 Err 在前
+    assert!(Ok::<u8, ()>(1).into_variant::<I0>().is_err());
+}
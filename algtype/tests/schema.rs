@@ -0,0 +1,110 @@
+use algtype::{
+    schema::{self, Describe, GenericDescribe, Schema},
+    Generic,
+};
+
+#[derive(Generic)]
+struct Config {
+    name: String,
+    size: (u8, i64),
+    ratio: Option<f32>,
+    tags: Vec<Tag>,
+}
+
+#[derive(Generic)]
+enum Tag {
+    Unit,
+    Tuple(char, bool),
+    Struct { r#type: u16 },
+}
+
+#[derive(Generic)]
+struct Point(i32, i32);
+
+// 递归的类型
+#[derive(Generic)]
+enum Expr {
+    Num(u8),
+    Neg(Vec<Expr>),
+    Pair(Option<Point>, Option<Vec<Expr>>),
+}
+
+impl GenericDescribe for Config {}
+impl GenericDescribe for Tag {}
+impl GenericDescribe for Point {}
+
+impl Describe for Expr {
+    fn describe(stack: &mut Vec<&'static str>) -> Schema {
+        schema::generic_describe::<Self>(stack)
+    }
+}
+
+#[test]
+fn test() {
+    let idl = "\
+struct Config {
+    name: string,
+    size: (u8, i64),
+    ratio: enum Option {
+        None,
+        Some(f32),
+    },
+    tags: [enum Tag {
+        Unit,
+        Tuple(char, bool),
+        Struct { type: u16 },
+    }],
+}";
+    assert_eq!(Config::schema().to_string(), idl);
+    assert_eq!(Point::schema().to_string(), "struct Point(i32, i32)");
+    assert_eq!(
+        Vec::<()>::schema(),
+        Schema::Seq(Box::new(Schema::Leaf("unit")))
+    );
+
+    let json = concat!(
+        r#"{"$defs":{"Tag":{"oneOf":[{"const":"Unit"},"#,
+        r#"{"type":"object","properties":{"Tuple":{"type":"array","prefixItems":[{"type":"string","minLength":1,"maxLength":1},{"type":"boolean"}],"minItems":2,"maxItems":2}},"required":["Tuple"],"additionalProperties":false},"#,
+        r#"{"type":"object","properties":{"Struct":{"type":"object","properties":{"type":{"type":"integer","minimum":0,"maximum":65535}},"required":["type"],"additionalProperties":false}},"required":["Struct"],"additionalProperties":false}]}},"#,
+        r##""$ref":"#/$defs/Tag"}"##,
+    );
+    assert_eq!(Tag::schema().to_json_schema(), json);
+    assert!(i64::schema()
+        .to_json_schema()
+        .contains(r#""minimum":-9223372036854775808,"maximum":9223372036854775807"#));
+
+    // 递归的类型，以及同名的不同类型
+    let idl = "\
+enum Expr {
+    Num(u8),
+    Neg([Expr]),
+    Pair(enum Option {
+        None,
+        Some(struct Point(i32, i32)),
+    }, enum Option {
+        None,
+        Some([Expr]),
+    }),
+}";
+    assert_eq!(Expr::schema().to_string(), idl);
+    let json = Expr::schema().to_json_schema();
+    let defs: Vec<_> = ["Expr", "Option", "Point", "Option_1"]
+        .iter()
+        .map(|k| json.find(&format!(r#""{k}":{{"#)).unwrap())
+        .collect();
+    assert!(defs.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(json.matches(r##"{"$ref":"#/$defs/Expr"}"##).count(), 2);
+    assert!(
+        json.contains(r##""prefixItems":[{"$ref":"#/$defs/Option"},{"$ref":"#/$defs/Option_1"}]"##)
+    );
+    assert!(json.ends_with(r##""$ref":"#/$defs/Expr"}"##));
+
+    // 取自递归类型内部的描述
+    let Schema::Adt(expr) = Expr::schema() else {
+        panic!()
+    };
+    assert_eq!(
+        expr.variants[1].fields[0].schema.to_json_schema(),
+        r##"{"type":"array","items":{"$ref":"#/$defs/Expr"}}"##
+    );
+}
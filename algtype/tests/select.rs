@@ -0,0 +1,44 @@
+use algtype::{
+    select::{GetByType, HasType},
+    Generic,
+};
+
+#[derive(Debug, PartialEq)]
+struct Logger(Vec<&'static str>);
+
+#[derive(Debug, PartialEq)]
+struct Metrics(u32);
+
+#[derive(Generic)]
+struct Plugin<'a> {
+    name: &'a str,
+    logger: Logger,
+    metrics: Metrics,
+}
+
+fn run<C, I, J>(cx: &mut C)
+where
+    C: HasType<Logger, I> + HasType<Metrics, J>,
+{
+    cx.get_by_type_mut::<Metrics, _>().0 += 1;
+    cx.get_by_type_mut::<Logger, _>().0.push("run");
+}
+
+#[test]
+fn test() {
+    let mut p = Plugin {
+        name: "p",
+        logger: Logger(vec![]),
+        metrics: Metrics(0),
+    };
+    run(&mut p);
+    run(&mut p);
+    assert_eq!(p.get_by_type::<Metrics, _>(), &Metrics(2));
+    assert_eq!(p.get_by_type::<Logger, _>().0, ["run", "run"]);
+    assert_eq!(*p.get_by_type::<&str, _>(), "p");
+
+    let mut t = (1u8, 'a', true);
+    *t.get_by_type_mut::<char, _>() = 'b';
+    assert_eq!(t, (1, 'b', true));
+    assert_eq!(*[1i32].get_by_type::<i32, _>(), 1);
+}
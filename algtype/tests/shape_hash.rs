@@ -0,0 +1,124 @@
+use algtype::shape_hash::ShapeHash;
+
+mod v1 {
+    use algtype::{shape_hash::GenericShapeHash, Generic};
+
+    #[derive(Generic)]
+    pub enum Msg {
+        Ping,
+        Data { id: u32, body: Vec<u8> },
+    }
+
+    impl GenericShapeHash for Msg {}
+}
+
+mod v2 {
+    use algtype::{shape_hash::GenericShapeHash, Generic};
+
+    // 增加变体
+    #[derive(Generic)]
+    pub enum Msg {
+        Ping,
+        Data { id: u32, body: Vec<u8> },
+        Close,
+    }
+
+    impl GenericShapeHash for Msg {}
+}
+
+mod v3 {
+    use algtype::{shape_hash::GenericShapeHash, Generic};
+
+    // 改变字段的类型
+    #[derive(Generic)]
+    pub enum Msg {
+        Ping,
+        Data { id: u64, body: Vec<u8> },
+    }
+
+    impl GenericShapeHash for Msg {}
+}
+
+mod v4 {
+    use algtype::{shape_hash::GenericShapeHash, Generic};
+
+    // 改名
+    #[derive(Generic)]
+    pub enum Msg {
+        Ping,
+        Data { id: u32, data: Vec<u8> },
+    }
+
+    impl GenericShapeHash for Msg {}
+}
+
+mod v5 {
+    use algtype::{shape_hash::GenericShapeHash, Generic};
+
+    // 改变字段类型内部的结构
+    #[derive(Generic)]
+    pub enum Msg {
+        Ping,
+        Data { id: u32, body: Vec<Body> },
+    }
+
+    #[derive(Generic)]
+    pub struct Body(u8, u8);
+
+    impl GenericShapeHash for Msg {}
+    impl GenericShapeHash for Body {}
+}
+
+mod same {
+    use algtype::{shape_hash::GenericShapeHash, Generic};
+
+    // 字段类型的写法不同
+    type Bytes = Vec<core::primitive::u8>;
+
+    #[derive(Generic)]
+    pub enum Msg {
+        Ping,
+        Data { id: u32, body: Bytes },
+    }
+
+    impl GenericShapeHash for Msg {}
+}
+
+mod generic {
+    use algtype::{
+        shape_hash::{GenericShapeHash, ShapeHash},
+        Generic,
+    };
+
+    #[derive(Generic)]
+    pub struct Msg<T> {
+        pub x: T,
+    }
+
+    impl<T: ShapeHash> GenericShapeHash for Msg<T> {}
+}
+
+#[test]
+fn test() {
+    const V1: u64 = v1::Msg::SHAPE_HASH;
+    assert_eq!(V1, same::Msg::SHAPE_HASH);
+    for h in [
+        v2::Msg::SHAPE_HASH,
+        v3::Msg::SHAPE_HASH,
+        v4::Msg::SHAPE_HASH,
+        v5::Msg::SHAPE_HASH,
+        <(u32, Vec<u8>)>::SHAPE_HASH,
+    ] {
+        assert_ne!(V1, h);
+    }
+    assert_ne!(
+        generic::Msg::<u32>::SHAPE_HASH,
+        generic::Msg::<String>::SHAPE_HASH
+    );
+    assert_ne!(Option::<u8>::SHAPE_HASH, Result::<u8, ()>::SHAPE_HASH);
+    assert_ne!(<()>::SHAPE_HASH, <(u8,)>::SHAPE_HASH);
+    assert_ne!(bool::SHAPE_HASH, Option::<()>::SHAPE_HASH);
+    assert_ne!(<(i32, i32)>::SHAPE_HASH, <[bool; 2]>::SHAPE_HASH);
+    assert_ne!(usize::SHAPE_HASH, u64::SHAPE_HASH);
+    assert_eq!(String::SHAPE_HASH, <&str>::SHAPE_HASH);
+}
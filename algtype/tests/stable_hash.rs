@@ -0,0 +1,21 @@
+use algtype::{
+    stable_hash::{stable_hash, GenericStableHash},
+    Generic,
+};
+
+#[derive(Generic)]
+enum Enum {
+    A,
+    B(u8, bool),
+    C { s: &'static str },
+}
+
+impl GenericStableHash for Enum {}
+
+#[test]
+fn test() {
+    // 结果不应随版本而变
+    assert_eq!(stable_hash(&Enum::A), 0x4d25767f9dce13f5);
+    assert_eq!(stable_hash(&Enum::B(1, true)), 0x138d43644500c9af);
+    assert_eq!(stable_hash(&Enum::C { s: "abc" }), 0xd3635e2afc6c21cc);
+}
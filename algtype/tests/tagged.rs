@@ -0,0 +1,189 @@
+use algtype::{
+    tagged::{self, Error, GenericTagged, Tagged},
+    Generic,
+};
+
+mod v1 {
+    use super::*;
+
+    #[derive(Generic, Debug, PartialEq)]
+    pub enum Event {
+        Start,
+        Move { x: i32, y: i32 },
+    }
+
+    #[derive(Generic, Debug, PartialEq)]
+    pub struct Log {
+        pub id: u64,
+        pub events: Vec<Event>,
+    }
+
+    impl GenericTagged for Event {}
+    impl GenericTagged for Log {}
+}
+
+mod v2 {
+    use super::*;
+
+    #[derive(Generic, Debug, PartialEq)]
+    pub enum Event {
+        Start,
+        Move { x: i32, y: i32, z: i32 },
+        Stop(String),
+    }
+
+    #[derive(Generic, Debug, PartialEq)]
+    pub struct Log {
+        pub id: u64,
+        pub events: Vec<Event>,
+        pub note: Option<String>,
+        pub scale: (f32, f64),
+    }
+
+    impl GenericTagged for Event {}
+    impl GenericTagged for Log {}
+}
+
+// 使用自己的默认值
+#[derive(Generic, Debug, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Server {
+            host: "localhost".into(),
+            port: 8080,
+        }
+    }
+}
+
+impl GenericTagged for Server {
+    fn tagged_default() -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+#[derive(Generic, Debug, PartialEq)]
+enum Never {}
+
+#[derive(Generic, Debug, PartialEq)]
+struct Strict(u8, Never);
+
+impl GenericTagged for Never {}
+impl GenericTagged for Strict {}
+
+#[test]
+fn test() {
+    // 编码的细节：长度、变体序号、键、值
+    assert_eq!(
+        tagged::to_bytes(&(300u16, -1i8)),
+        [6, 0, 8, 0xac, 0x02, 16, 1]
+    );
+    assert_eq!(
+        tagged::to_bytes(&Some("é".to_string())),
+        [5, 1, 10, 2, 0xc3, 0xa9]
+    );
+    assert_eq!(tagged::to_bytes(&None::<u8>), [1, 0]);
+
+    for x in [0i128, -1, i128::MIN, i128::MAX] {
+        assert_eq!(tagged::from_bytes::<i128>(&tagged::to_bytes(&x)), Ok(x));
+    }
+    assert_eq!(
+        tagged::from_bytes::<u128>(&tagged::to_bytes(&u128::MAX)),
+        Ok(u128::MAX)
+    );
+
+    let old = v1::Log {
+        id: 1,
+        events: vec![v1::Event::Start, v1::Event::Move { x: -3, y: 4 }],
+    };
+    let bytes = tagged::to_bytes(&old);
+    assert_eq!(tagged::from_bytes::<v1::Log>(&bytes).as_ref(), Ok(&old));
+
+    // 旧数据缺少的字段取默认值
+    let new: v2::Log = tagged::from_bytes(&bytes).unwrap();
+    let expected = v2::Log {
+        id: 1,
+        events: vec![v2::Event::Start, v2::Event::Move { x: -3, y: 4, z: 0 }],
+        note: None,
+        scale: (0.0, 0.0),
+    };
+    assert_eq!(new, expected);
+
+    // 新数据多出的字段被跳过
+    let new = v2::Log {
+        note: Some("n".into()),
+        scale: (1.5, -2.0),
+        events: vec![v2::Event::Move { x: 1, y: 2, z: 3 }],
+        ..new
+    };
+    let bytes = tagged::to_bytes(&new);
+    assert_eq!(tagged::from_bytes::<v2::Log>(&bytes).as_ref(), Ok(&new));
+    let old: v1::Log = tagged::from_bytes(&bytes).unwrap();
+    assert_eq!(old.events, [v1::Event::Move { x: 1, y: 2 }]);
+
+    // 未知的变体是错误
+    let new = v2::Log {
+        events: vec![v2::Event::Stop("s".into())],
+        ..new
+    };
+    let bytes = tagged::to_bytes(&new);
+    assert_eq!(
+        tagged::from_bytes::<v1::Log>(&bytes),
+        Err(Error::UnknownVariant(2))
+    );
+
+    // 缺少的字段取类型自己的默认值
+    let server: Server = tagged::from_bytes(&tagged::to_bytes(&("a".to_string(),))).unwrap();
+    assert_eq!(
+        server,
+        Server {
+            host: "a".into(),
+            port: 8080
+        }
+    );
+    let (id, server): (u8, Server) = tagged::from_bytes(&tagged::to_bytes(&(1u8,))).unwrap();
+    assert_eq!((id, server), (1, Server::default()));
+    let server = Server {
+        host: "b".into(),
+        port: 0,
+    };
+    assert_eq!(
+        tagged::from_bytes::<Server>(&tagged::to_bytes(&server)),
+        Ok(server)
+    );
+
+    // 其他错误
+    assert_eq!(Strict::default_value(), None);
+    assert_eq!(
+        tagged::from_bytes::<Strict>(&[3, 0, 8, 1]),
+        Err(Error::MissingField(2))
+    );
+    assert_eq!(
+        tagged::from_bytes::<(u8,)>(&[3, 0, 10, 0])
+            .unwrap_err()
+            .to_string(),
+        "field 1: expected Varint, found Len"
+    );
+    assert_eq!(tagged::from_bytes::<(u8,)>(&[3, 0, 8]), Err(Error::Eof));
+    assert_eq!(
+        tagged::from_bytes::<(u8,)>(&[3, 0, 8, 1, 0]),
+        Err(Error::TrailingBytes)
+    );
+    assert_eq!(
+        tagged::from_bytes::<(u8,)>(&[3, 0, 11, 1]),
+        Err(Error::UnknownWire(3))
+    );
+    assert_eq!(
+        tagged::from_bytes::<(u8,)>(&[4, 0, 8, 0x80, 2]),
+        Err(Error::Overflow)
+    );
+    assert_eq!(
+        tagged::from_bytes::<bool>(&[2]),
+        Err(Error::Invalid("bool"))
+    );
+    assert_eq!(tagged::from_bytes::<u8>(&[0xff; 20]), Err(Error::Overflow));
+}
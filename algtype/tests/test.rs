@@ -1,7 +1,9 @@
-use algtype::{Generic, One, Product, Repr, Sum, Zero};
-use proptest::{arbitrary::Arbitrary, proptest};
+use algtype::{
+    testing::{assert_refl, check_refl},
+    Generic, One, Product, Sum, Zero,
+};
+use proptest::arbitrary::Arbitrary;
 use proptest_derive::Arbitrary;
-use std::fmt::Debug;
 
 #[derive(Clone, Generic, Debug, PartialEq, Arbitrary)]
 struct Unit;
@@ -35,34 +37,15 @@ enum Ref<'a> {
     Ref(&'a str),
 }
 
-fn check<T: Generic<Repr = R> + PartialEq + Debug + Clone + Arbitrary, R: Repr>()
-where
-    for<'a> R::Ref<'a>: PartialEq + Debug,
-    for<'a> R::Mut<'a>: PartialEq + Debug,
-{
-    proptest!(|(x: T)| assert_refl(x));
-}
-
-fn assert_refl<T: Generic<Repr = R> + PartialEq + Debug + Clone, R: Repr>(mut x: T)
-where
-    for<'a> R::Ref<'a>: PartialEq + Debug,
-    for<'a> R::Mut<'a>: PartialEq + Debug,
-{
-    let mut repr = x.clone().into_repr();
-    assert_eq!(x.as_repr(), repr.as_ref());
-    assert_eq!(x.as_mut_repr(), repr.as_mut_ref());
-    assert_eq!(x, T::from_repr(repr));
-}
-
 #[test]
 fn test() {
-    check::<Unit, Sum<One, Zero>>();
-    check::<Tuple<_>, Sum<Product<bool, Product<Unit, Product<Enum<bool>, One>>>, Zero>>();
-    check::<
+    check_refl::<Unit, Sum<One, Zero>>();
+    check_refl::<Tuple<_>, Sum<Product<bool, Product<Unit, Product<Enum<bool>, One>>>, Zero>>();
+    check_refl::<
         Struct<_, std::ops::Range<_>>,
         Sum<Product<i32, Product<Unit, Product<Enum<i32>, Product<i32, One>>>>, Zero>,
     >();
-    check::<
+    check_refl::<
         Enum<()>,
         Sum<
             One,
@@ -0,0 +1,92 @@
+use algtype::{
+    variant_name::VariantName, Generic, GenericDisplay, GenericFromStr, One, Repr, Sum, Zero,
+};
+
+#[derive(Clone, Copy, Generic, GenericDisplay, GenericFromStr, Debug, PartialEq)]
+enum Color {
+    Red,
+    DarkBlue,
+}
+
+#[derive(Clone, Copy, Generic, GenericDisplay, GenericFromStr, Debug, PartialEq)]
+#[algtype(rename_all = "snake_case")]
+enum Mode {
+    ReadOnly,
+    ReadWrite,
+    Proxy,
+}
+
+#[derive(Generic)]
+#[algtype(rename_all = "SCREAMING_SNAKE_CASE")]
+struct Config {
+    max_size: u32,
+}
+
+// 手写的实现，没有名字信息
+#[derive(Clone, Copy, GenericDisplay, GenericFromStr, Debug, PartialEq)]
+enum Toggle {
+    Off,
+    On,
+}
+
+impl Generic for Toggle {
+    type Repr = Sum<One, Sum<One, Zero>>;
+
+    fn into_repr(self) -> Self::Repr {
+        match self {
+            Toggle::Off => Sum::This(One),
+            Toggle::On => Sum::Next(Sum::This(One)),
+        }
+    }
+
+    fn from_repr(repr: Self::Repr) -> Self {
+        match repr {
+            Sum::This(One) => Toggle::Off,
+            Sum::Next(Sum::This(One)) => Toggle::On,
+            Sum::Next(Sum::Next(x)) => match x {},
+        }
+    }
+
+    fn as_repr(&self) -> <Self::Repr as Repr>::Ref<'_> {
+        self.into_repr()
+    }
+
+    fn as_mut_repr(&mut self) -> <Self::Repr as Repr>::Mut<'_> {
+        self.into_repr()
+    }
+}
+
+#[test]
+fn test() {
+    assert_eq!(Color::DarkBlue.variant_name(), Some("DarkBlue"));
+    assert_eq!(Color::from_variant_name("Red"), Some(Color::Red));
+    assert_eq!(Color::from_variant_name("red"), None);
+    assert_eq!(Color::Red.to_string(), "Red");
+
+    for m in (0..).map_while(Mode::from_variant_index) {
+        assert_eq!(m.to_string().parse(), Ok(m));
+    }
+    assert_eq!(Mode::ReadWrite.to_string(), "read_write");
+    assert_eq!(Mode::Proxy.variant_index(), 2);
+    assert_eq!(Mode::from_variant_index(3), None);
+
+    let e = "write".parse::<Mode>().unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "unknown variant of Mode, expected one of: read_only, read_write, proxy"
+    );
+
+    assert_eq!(bool::from_variant_name("true"), Some(true));
+    assert_eq!(().variant_name(), None);
+    assert_eq!(Toggle::On.variant_name(), None);
+    assert_eq!(Toggle::On.to_string(), "1");
+    assert_eq!("0".parse(), Ok(Toggle::Off));
+    assert_eq!(
+        "2".parse::<Toggle>().unwrap_err().to_string(),
+        "unknown variant"
+    );
+    assert_eq!(
+        Config::META.unwrap().variants[0].fields,
+        algtype::FieldsMeta::Named(&["MAX_SIZE"])
+    );
+}
@@ -0,0 +1,62 @@
+use algtype::{
+    utils::{cast, view, view_mut},
+    Generic,
+};
+
+#[derive(Generic, Debug, PartialEq)]
+#[algtype(view = EventRef, mut_view = EventMut)]
+pub enum Event<'a, T> {
+    Key(char),
+    Click { x: i32, y: i32 },
+    Custom(&'a str, T),
+    Quit,
+}
+
+#[derive(Generic)]
+#[algtype(view = PairRef)]
+struct Pair(u8, String);
+
+// 变体的属性和显式的判别值不会复制到引用形式
+#[derive(Generic, Default, Debug, PartialEq)]
+#[algtype(view = LevelRef)]
+#[repr(u8)]
+enum Level {
+    #[default]
+    Low = 1,
+    High(u8) = 5,
+}
+
+#[test]
+fn test() {
+    let mut e = Event::Click { x: 1, y: 2 };
+    let EventRef::Click { x, y } = view(&e) else {
+        panic!()
+    };
+    assert_eq!((*x, *y), (1, 2));
+    let r: EventRef<'_, '_, bool> = cast(e.as_repr());
+    assert!(matches!(r, EventRef::Click { x: 1, .. }));
+
+    if let EventMut::Click { x, .. } = view_mut(&mut e) {
+        *x = 10;
+    }
+    assert_eq!(e, Event::Click { x: 10, y: 2 });
+
+    let mut e = Event::Custom("a", vec![1]);
+    match view_mut(&mut e) {
+        EventMut::Custom(s, v) => {
+            *s = "b";
+            v.push(2);
+        }
+        _ => panic!(),
+    }
+    assert_eq!(e, Event::Custom("b", vec![1, 2]));
+    assert!(matches!(view(&Event::<()>::Quit), EventRef::Quit));
+
+    let p = Pair(1, "x".into());
+    let PairRef(a, b) = view(&p);
+    assert_eq!((*a, b.as_str()), (1, "x"));
+    assert_eq!(PairRef::META.unwrap().name, "PairRef");
+
+    assert_eq!(Level::default(), Level::Low);
+    assert!(matches!(view(&Level::High(3)), LevelRef::High(3)));
+}
@@ -0,0 +1,102 @@
+//! 容器上的 `algtype` 属性
+
+use proc_macro2::Ident;
+use syn::{DeriveInput, LitStr};
+
+#[derive(Default)]
+pub struct Attrs {
+    /// `view = FooRef`
+    pub view: Option<Ident>,
+    /// `mut_view = FooMut`
+    pub mut_view: Option<Ident>,
+    /// `rename_all = "snake_case"`
+    pub rename_all: Option<Case>,
+}
+
+impl Attrs {
+    pub fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        for a in input.attrs.iter().filter(|a| a.path().is_ident("algtype")) {
+            a.parse_nested_meta(|m| {
+                if m.path.is_ident("view") {
+                    attrs.view = Some(m.value()?.parse()?);
+                    Ok(())
+                } else if m.path.is_ident("mut_view") {
+                    attrs.mut_view = Some(m.value()?.parse()?);
+                    Ok(())
+                } else if m.path.is_ident("rename_all") {
+                    let s: LitStr = m.value()?.parse()?;
+                    let case = Case::parse(&s.value())
+                        .ok_or_else(|| syn::Error::new(s.span(), "unknown case"))?;
+                    attrs.rename_all = Some(case);
+                    Ok(())
+                } else {
+                    Err(m.error("unsupported algtype attribute"))
+                }
+            })?;
+        }
+        Ok(attrs)
+    }
+}
+
+/// 命名风格
+#[derive(Clone, Copy)]
+pub enum Case {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+}
+
+impl Case {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "lowercase" => Case::Lower,
+            "UPPERCASE" => Case::Upper,
+            "PascalCase" => Case::Pascal,
+            "camelCase" => Case::Camel,
+            "snake_case" => Case::Snake,
+            "SCREAMING_SNAKE_CASE" => Case::ScreamingSnake,
+            "kebab-case" => Case::Kebab,
+            _ => return None,
+        })
+    }
+
+    /// 转换 `PascalCase` 或 `snake_case` 的名字
+    pub fn apply(self, name: &str) -> String {
+        // 按下划线和大写字母分词
+        let mut words: Vec<String> = vec![];
+        for part in name.split('_').filter(|p| !p.is_empty()) {
+            let mut word = String::new();
+            for c in part.chars() {
+                if c.is_uppercase() && !word.is_empty() {
+                    words.push(word);
+                    word = String::new();
+                }
+                word.extend(c.to_lowercase());
+            }
+            words.push(word);
+        }
+        let capitalize = |w: &String| -> String {
+            let mut cs = w.chars();
+            cs.next()
+                .map(|c| c.to_uppercase().chain(cs).collect())
+                .unwrap_or_default()
+        };
+        match self {
+            Case::Lower => words.concat(),
+            Case::Upper => words.concat().to_uppercase(),
+            Case::Pascal => words.iter().map(capitalize).collect(),
+            Case::Camel => {
+                let rest: String = words.iter().skip(1).map(capitalize).collect();
+                words.first().cloned().unwrap_or_default() + &rest
+            }
+            Case::Snake => words.join("_"),
+            Case::ScreamingSnake => words.join("_").to_uppercase(),
+            Case::Kebab => words.join("-"),
+        }
+    }
+}
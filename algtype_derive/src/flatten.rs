@@ -0,0 +1,425 @@
+//! 含有 `#[algtype(flatten)]` 字段者的实现
+
+use crate::{attrs::Case, fields_meta, fields_val, rename, xn, Algtype};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, Field, Fields, GenericParam, Generics, Type, WherePredicate};
+
+/// 字段是否需要展开
+pub fn is_flatten(field: &Field) -> syn::Result<bool> {
+    let mut flatten = false;
+    for a in field.attrs.iter().filter(|a| a.path().is_ident("algtype")) {
+        a.parse_nested_meta(|m| {
+            if m.path.is_ident("flatten") {
+                flatten = true;
+                Ok(())
+            } else {
+                Err(m.error("unsupported algtype attribute"))
+            }
+        })?;
+    }
+    Ok(flatten)
+}
+
+struct Var<'a> {
+    ctor: TokenStream,
+    /// 变体在名字信息中的名字
+    name: String,
+    kind: &'a Fields,
+    fields: Vec<(&'a Type, bool)>,
+}
+
+impl Var<'_> {
+    /// 若变体仅有一个展开的字段，则返回其类型
+    fn sum_flatten(&self) -> Option<&Type> {
+        match self.fields[..] {
+            [(ty, true)] => Some(ty),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Own,
+    Ref,
+    Mut,
+}
+
+impl Mode {
+    fn method(self, name: &str) -> Ident {
+        match self {
+            Mode::Own => format_ident!("{name}"),
+            Mode::Ref => format_ident!("{name}_ref"),
+            Mode::Mut => format_ident!("{name}_mut"),
+        }
+    }
+
+    fn to_repr(self) -> Ident {
+        match self {
+            Mode::Own => format_ident!("into_repr"),
+            Mode::Ref => format_ident!("as_repr"),
+            Mode::Mut => format_ident!("as_mut_repr"),
+        }
+    }
+}
+
+fn field_repr(path: &TokenStream, ty: &Type) -> TokenStream {
+    quote!(<#ty as #path::Generic>::Repr)
+}
+
+fn product_ty(path: &TokenStream, fields: &[(&Type, bool)]) -> TokenStream {
+    fields
+        .iter()
+        .rfold(quote!(#path::One), |rest, &(ty, flat)| match flat {
+            true => {
+                let r = field_repr(path, ty);
+                quote!(<<#r as #path::flatten::Single>::Product as #path::flatten::Append<#rest>>::Output)
+            }
+            false => quote!(#path::Product<#ty, #rest>),
+        })
+}
+
+fn sum_ty(path: &TokenStream, vars: &[Var]) -> TokenStream {
+    vars.iter()
+        .rfold(quote!(#path::Zero), |rest, v| match v.sum_flatten() {
+            Some(ty) => {
+                let r = field_repr(path, ty);
+                quote!(<#r as #path::flatten::Concat<#rest>>::Output)
+            }
+            None => {
+                let p = product_ty(path, &v.fields);
+                quote!(#path::Sum<#p, #rest>)
+            }
+        })
+}
+
+fn product_val(path: &TokenStream, fields: &[(&Type, bool)], mode: Mode) -> TokenStream {
+    let (to, unwrap, append) = (mode.to_repr(), mode.method("unwrap"), mode.method("append"));
+    let mut val = quote!(#path::One);
+    for (i, &(ty, flat)) in fields.iter().enumerate().rev() {
+        let x = xn(i);
+        val = match flat {
+            true => {
+                let r = field_repr(path, ty);
+                let rest = product_ty(path, &fields[i + 1..]);
+                quote! {
+                    <<#r as #path::flatten::Single>::Product as #path::flatten::Append<#rest>>::#append(
+                        <#r as #path::flatten::Single>::#unwrap(<#ty as #path::Generic>::#to(#x)),
+                        #val,
+                    )
+                }
+            }
+            false => quote!(#path::Product(#x, #val)),
+        };
+    }
+    val
+}
+
+fn sum_val(path: &TokenStream, vars: &[Var], i: usize, mode: Mode) -> TokenStream {
+    let (to, inl, inr) = (mode.to_repr(), mode.method("inl"), mode.method("inr"));
+    let rest = sum_ty(path, &vars[i + 1..]);
+    let mut val = match vars[i].sum_flatten() {
+        Some(ty) => {
+            let r = field_repr(path, ty);
+            let x = xn(0);
+            quote!(<#r as #path::flatten::Concat<#rest>>::#inl(<#ty as #path::Generic>::#to(#x)))
+        }
+        None => {
+            let p = product_val(path, &vars[i].fields, mode);
+            quote!(#path::Sum::This(#p))
+        }
+    };
+    for j in (0..i).rev() {
+        val = match vars[j].sum_flatten() {
+            Some(ty) => {
+                let r = field_repr(path, ty);
+                let rest = sum_ty(path, &vars[j + 1..]);
+                quote!(<#r as #path::flatten::Concat<#rest>>::#inr(#val))
+            }
+            None => quote!(#path::Sum::Next(#val)),
+        };
+    }
+    val
+}
+
+fn product_from(path: &TokenStream, var: &Var) -> TokenStream {
+    let stmts = var.fields.iter().enumerate().map(|(i, &(ty, flat))| {
+        let x = xn(i);
+        match flat {
+            true => {
+                let r = field_repr(path, ty);
+                let rest = product_ty(path, &var.fields[i + 1..]);
+                quote! {
+                    let (#x, p) =
+                        <<#r as #path::flatten::Single>::Product as #path::flatten::Append<#rest>>::split(p);
+                    let #x = <#ty as #path::Generic>::from_repr(
+                        <#r as #path::flatten::Single>::wrap(#x),
+                    );
+                }
+            }
+            false => quote!(let #path::Product(#x, p) = p;),
+        }
+    });
+    let ctor = &var.ctor;
+    let val = fields_val(var.kind);
+    quote! {
+        #(#stmts)*
+        let #path::One = p;
+        #ctor #val
+    }
+}
+
+fn sum_from(path: &TokenStream, vars: &[Var], i: usize, expr: TokenStream) -> TokenStream {
+    let Some(var) = vars.get(i) else {
+        return quote!(match #expr {});
+    };
+    let next = sum_from(path, vars, i + 1, quote!(r));
+    match var.sum_flatten() {
+        Some(ty) => {
+            let r = field_repr(path, ty);
+            let rest = sum_ty(path, &vars[i + 1..]);
+            let (ctor, val, x) = (&var.ctor, fields_val(var.kind), xn(0));
+            quote! {
+                match <#r as #path::flatten::Concat<#rest>>::split(#expr) {
+                    #path::Sum::This(#x) => {
+                        let #x = <#ty as #path::Generic>::from_repr(#x);
+                        #ctor #val
+                    }
+                    #path::Sum::Next(r) => #next,
+                }
+            }
+        }
+        None => {
+            let body = product_from(path, var);
+            quote! {
+                match #expr {
+                    #path::Sum::This(p) => { #body }
+                    #path::Sum::Next(r) => #next,
+                }
+            }
+        }
+    }
+}
+
+/// 各展开的字段所需的约束
+fn bounds(path: &TokenStream, vars: &[Var]) -> Vec<WherePredicate> {
+    let mut preds: Vec<WherePredicate> = Vec::new();
+    for (i, var) in vars.iter().enumerate() {
+        if let Some(ty) = var.sum_flatten() {
+            let r = field_repr(path, ty);
+            let rest = sum_ty(path, &vars[i + 1..]);
+            preds.push(syn::parse_quote!(#r: #path::flatten::Concat<#rest>));
+            continue;
+        }
+        for (j, &(ty, flat)) in var.fields.iter().enumerate() {
+            if flat {
+                let r = field_repr(path, ty);
+                let rest = product_ty(path, &var.fields[j + 1..]);
+                preds.push(syn::parse_quote!(#r: #path::flatten::Single));
+                preds.push(syn::parse_quote! {
+                    <#r as #path::flatten::Single>::Product: #path::flatten::Append<#rest>
+                });
+            }
+        }
+    }
+    preds
+}
+
+/// 类型是否用到了泛型参数
+fn uses_generics(ty: &Type, generics: &Generics) -> bool {
+    let params: Vec<String> = generics
+        .params
+        .iter()
+        .map(|p| match p {
+            GenericParam::Type(t) => t.ident.to_string(),
+            GenericParam::Lifetime(l) => l.lifetime.ident.to_string(),
+            GenericParam::Const(c) => c.ident.to_string(),
+        })
+        .collect();
+    fn walk(tokens: TokenStream, params: &[String]) -> bool {
+        tokens.into_iter().any(|t| match t {
+            TokenTree::Ident(i) => params.contains(&i.to_string()),
+            TokenTree::Group(g) => walk(g.stream(), params),
+            _ => false,
+        })
+    }
+    walk(quote!(#ty), &params)
+}
+
+/// 名字信息，在编译期拼接内层的名字信息
+///
+/// 常量中无法引用外层的泛型参数，因此展开的字段的类型用到它们时，返回 `None`。
+fn meta(
+    path: &TokenStream,
+    name: &Ident,
+    data: &Algtype,
+    vars: &[Var],
+    case: Option<Case>,
+    generics: &Generics,
+) -> TokenStream {
+    let flat_tys = vars.iter().flat_map(|v| &v.fields).filter(|f| f.1);
+    if flat_tys.clone().any(|&(ty, _)| uses_generics(ty, generics)) {
+        return quote!(::core::option::Option::None);
+    }
+    // `rename_all` 作用于结构体的字段名与枚举的变体名
+    let (is_enum, field_case) = match data {
+        Algtype::Struct(_) => (false, case),
+        Algtype::Enum(_) => (true, None),
+    };
+    let meta_of = |ty: &Type| quote!(<#ty as #path::Generic>::META);
+    let mut items = Vec::new();
+    let parts: Vec<_> = vars
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            if let Some(ty) = v.sum_flatten() {
+                let meta = meta_of(ty);
+                return quote!(#path::flatten::meta_variants(#meta));
+            }
+            let fields = match v.kind {
+                Fields::Named(f) if v.fields.iter().any(|f| f.1) => {
+                    let names = f.named.iter().zip(&v.fields).map(|(f, &(ty, flat))| {
+                        if flat {
+                            let meta = meta_of(ty);
+                            return quote!(#path::flatten::meta_fields(#meta));
+                        }
+                        let name =
+                            rename(&f.ident.as_ref().unwrap().unraw().to_string(), field_case);
+                        quote!(::core::option::Option::Some(&[#name]))
+                    });
+                    let (parts, all) =
+                        (format_ident!("__FIELDS_{i}"), format_ident!("__NAMES_{i}"));
+                    let len = v.fields.len();
+                    items.push(quote! {
+                        const #parts: [::core::option::Option<&[&str]>; #len] = [#(#names),*];
+                        const #all: [&str; #path::flatten::total_len(&#parts)] =
+                            #path::flatten::concat(&#parts, "");
+                    });
+                    // 内层的字段没有名字时，视为匿名字段
+                    quote! {
+                        match #path::flatten::all_some(&#parts) {
+                            true => #path::FieldsMeta::Named(&#all),
+                            false => #path::FieldsMeta::Unnamed,
+                        }
+                    }
+                }
+                kind => fields_meta(path, kind, field_case),
+            };
+            let (variant, name) = (format_ident!("__VARIANT_{i}"), &v.name);
+            items.push(quote! {
+                const #variant: [#path::VariantMeta; 1] =
+                    [#path::VariantMeta { name: #name, fields: #fields }];
+            });
+            quote!(::core::option::Option::Some(&#variant))
+        })
+        .collect();
+    let len = vars.len();
+    let name = name.unraw().to_string();
+    quote!({
+        #(#items)*
+        const __PARTS: [::core::option::Option<&[#path::VariantMeta]>; #len] = [#(#parts),*];
+        const __VARIANTS: [#path::VariantMeta; #path::flatten::total_len(&__PARTS)] =
+            #path::flatten::concat(
+                &__PARTS,
+                #path::VariantMeta { name: "", fields: #path::FieldsMeta::Unit },
+            );
+        const __META: #path::Meta = #path::Meta {
+            name: #name,
+            is_enum: #is_enum,
+            variants: &__VARIANTS,
+        };
+        match #path::flatten::all_some(&__PARTS) {
+            true => ::core::option::Option::Some(&__META),
+            false => ::core::option::Option::None,
+        }
+    })
+}
+
+pub fn generic(
+    path: &TokenStream,
+    name: &Ident,
+    generics: &Generics,
+    data: &Algtype,
+    case: Option<Case>,
+) -> syn::Result<TokenStream> {
+    fn var(ctor: TokenStream, name: String, kind: &Fields) -> syn::Result<Var<'_>> {
+        let fields = kind
+            .iter()
+            .map(|f| Ok((&f.ty, is_flatten(f)?)))
+            .collect::<syn::Result<_>>()?;
+        Ok(Var {
+            ctor,
+            name,
+            kind,
+            fields,
+        })
+    }
+    let vars: Vec<Var> = match data {
+        Algtype::Struct(f) => vec![var(quote!(Self), name.unraw().to_string(), f)?],
+        Algtype::Enum(v) => v
+            .iter()
+            .map(|v| {
+                let ident = &v.ident;
+                let name = rename(&ident.unraw().to_string(), case);
+                var(quote!(Self::#ident), name, &v.fields)
+            })
+            .collect::<syn::Result<_>>()?,
+    };
+    let meta = meta(path, name, data, &vars, case, generics);
+
+    let mut generics = generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .extend(bounds(path, &vars));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let repr_ty = sum_ty(path, &vars);
+    let stru: Vec<_> = vars
+        .iter()
+        .map(|v| {
+            let (ctor, val) = (&v.ctor, fields_val(v.kind));
+            quote!(#ctor #val)
+        })
+        .collect();
+    let [own, refs, muts] = [Mode::Own, Mode::Ref, Mode::Mut].map(|mode| {
+        (0..vars.len())
+            .map(|i| sum_val(path, &vars, i, mode))
+            .collect::<Vec<_>>()
+    });
+    let from = sum_from(path, &vars, 0, quote!(repr));
+
+    Ok(quote! {
+        impl #impl_generics #path::Generic for #name #ty_generics #where_clause {
+            type Repr = #repr_ty;
+            const META: ::core::option::Option<&'static #path::Meta> = #meta;
+
+            #[inline]
+            fn into_repr(self) -> Self::Repr {
+                match self {
+                    #(#stru => #own,)*
+                }
+            }
+
+            #[inline]
+            fn from_repr(repr: Self::Repr) -> Self {
+                #from
+            }
+
+            #[inline]
+            fn as_repr(&self) -> <Self::Repr as #path::Repr>::Ref<'_> {
+                match self {
+                    #(#stru => #refs,)*
+                }
+            }
+
+            #[inline]
+            fn as_mut_repr(&mut self) -> <Self::Repr as #path::Repr>::Mut<'_> {
+                match self {
+                    #(#stru => #muts,)*
+                }
+            }
+        }
+    })
+}
@@ -1,6 +1,14 @@
+mod attrs;
+mod flatten;
+mod view;
+
+use attrs::Case;
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use syn::{Data, DeriveInput, Field, Fields, Variant};
+use syn::{
+    ext::IdentExt, parse_quote, punctuated::Punctuated, Data, DeriveInput, Field, Fields, Token,
+    Variant, WherePredicate,
+};
 
 enum Algtype {
     Struct(Fields),
@@ -79,22 +87,108 @@ fn algtype_val(path: &TokenStream, data: &Algtype) -> (Vec<TokenStream>, Vec<Tok
     }
 }
 
+fn rename(name: &str, case: Option<Case>) -> String {
+    case.map_or_else(|| name.into(), |c| c.apply(name))
+}
+fn fields_meta(path: &TokenStream, fields: &Fields, case: Option<Case>) -> TokenStream {
+    match fields {
+        Fields::Named(f) => {
+            let names = f
+                .named
+                .iter()
+                .map(|f| rename(&f.ident.as_ref().unwrap().unraw().to_string(), case));
+            quote!(#path::FieldsMeta::Named(&[#(#names),*]))
+        }
+        Fields::Unnamed(_) => quote!(#path::FieldsMeta::Unnamed),
+        Fields::Unit => quote!(#path::FieldsMeta::Unit),
+    }
+}
+fn variant_meta(
+    path: &TokenStream,
+    name: String,
+    fields: &Fields,
+    case: Option<Case>,
+) -> TokenStream {
+    let fields = fields_meta(path, fields, case);
+    quote!(#path::VariantMeta { name: #name, fields: #fields })
+}
+fn meta(path: &TokenStream, name: &Ident, data: &Algtype, case: Option<Case>) -> TokenStream {
+    // `rename_all` 作用于结构体的字段名与枚举的变体名
+    let (is_enum, variants) = match data {
+        Algtype::Struct(f) => (
+            false,
+            vec![variant_meta(path, name.unraw().to_string(), f, case)],
+        ),
+        Algtype::Enum(v) => (
+            true,
+            v.iter()
+                .map(|v| {
+                    let name = rename(&v.ident.unraw().to_string(), case);
+                    variant_meta(path, name, &v.fields, None)
+                })
+                .collect(),
+        ),
+    };
+    let name = name.unraw().to_string();
+    quote! {
+        #path::Meta {
+            name: #name,
+            is_enum: #is_enum,
+            variants: &[#(#variants),*],
+        }
+    }
+}
+
 fn m(path: &TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    expand(path, &input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(path: &TokenStream, input: &DeriveInput) -> syn::Result<TokenStream> {
+    let attrs = attrs::Attrs::parse(input)?;
+    let mut tokens = generic(path, input, attrs.rename_all)?;
+    for (name, mutable) in [(attrs.view, false), (attrs.mut_view, true)] {
+        if let Some(name) = name {
+            let v = view::view(input, &name, mutable)?;
+            tokens.extend(view::define(&v));
+            tokens.extend(generic(path, &v, attrs.rename_all)?);
+        }
+    }
+    Ok(tokens)
+}
+
+fn generic(
+    path: &TokenStream,
+    input: &DeriveInput,
+    case: Option<Case>,
+) -> syn::Result<TokenStream> {
+    let name = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let data = match input.data {
-        Data::Struct(data) => Algtype::Struct(data.fields),
-        Data::Enum(data) => Algtype::Enum(data.variants.into_iter().collect()),
-        Data::Union(_) => panic!("union is unsupported"),
+    let data = match &input.data {
+        Data::Struct(data) => Algtype::Struct(data.fields.clone()),
+        Data::Enum(data) => Algtype::Enum(data.variants.iter().cloned().collect()),
+        Data::Union(_) => return Err(syn::Error::new_spanned(name, "union is unsupported")),
+    };
+    let fields: Vec<&Field> = match &data {
+        Algtype::Struct(f) => f.iter().collect(),
+        Algtype::Enum(v) => v.iter().flat_map(|v| &v.fields).collect(),
     };
+    for f in fields {
+        if flatten::is_flatten(f)? {
+            return flatten::generic(path, name, &input.generics, &data, case);
+        }
+    }
     let repr_ty = repr_ty(path, &data);
+    let meta = meta(path, name, &data, case);
     let (stru, repr) = algtype_val(path, &data);
 
-    quote! {
+    Ok(quote! {
         impl #impl_generics #path::Generic for #name #ty_generics #where_clause {
             type Repr = #repr_ty;
+            const META: Option<&'static #path::Meta> = Some(&#meta);
 
             #[inline]
             fn into_repr(self) -> Self::Repr {
@@ -128,12 +222,16 @@ fn m(path: &TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStr
                 }
             }
         }
-    }
-    .into()
+    })
 }
 
 /// 在 struct 或 enum 上实现 `Generic`
-#[proc_macro_derive(Generic)]
+///
+/// 字段可加 `#[algtype(flatten)]`，见 `algtype::flatten`。
+///
+/// 类型可加 `#[algtype(view = FooRef, mut_view = FooMut)]`，以生成引用形式，
+/// 其字段为原字段的引用，表示为原表示的 `Repr::Ref` 或 `Repr::Mut`。
+#[proc_macro_derive(Generic, attributes(algtype))]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     m(&quote!(::algtype), input)
 }
@@ -144,3 +242,236 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 pub fn impl_generic(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     m(&quote!(crate), input)
 }
+
+/// 实现标记 trait，其约束为各字段实现 `bound`，或由 `attr` 属性给出
+fn marker(
+    input: proc_macro::TokenStream,
+    marker: TokenStream,
+    bound: TokenStream,
+    attr: &str,
+) -> proc_macro::TokenStream {
+    let mut input = syn::parse_macro_input!(input as DeriveInput);
+    if let Data::Union(_) = input.data {
+        return syn::Error::new_spanned(&input.ident, "union is unsupported")
+            .into_compile_error()
+            .into();
+    }
+    let mut given = None;
+    for a in input.attrs.iter().filter(|a| a.path().is_ident(attr)) {
+        let preds =
+            match a.parse_args_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated) {
+                Ok(preds) => preds,
+                Err(e) => return e.into_compile_error().into(),
+            };
+        given.get_or_insert_with(Vec::new).extend(preds);
+    }
+    let preds = given.unwrap_or_else(|| {
+        let fields: Vec<&Field> = match &input.data {
+            Data::Struct(data) => data.fields.iter().collect(),
+            Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+            Data::Union(_) => unreachable!(),
+        };
+        fields
+            .into_iter()
+            .map(|Field { ty, .. }| parse_quote!(#ty: #bound))
+            .collect()
+    });
+    input.generics.make_where_clause().predicates.extend(preds);
+
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote!(impl #impl_generics #marker for #name #ty_generics #where_clause {}).into()
+}
+
+/// 在各变体都没有字段的 enum 上实现 `Display`，输出 `algtype::variant_name::VariantName::variant_name`
+///
+/// 没有名字信息时输出变体的序号。
+#[proc_macro_derive(GenericDisplay)]
+pub fn derive_display(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let path = quote!(::algtype::variant_name::VariantName);
+    quote! {
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match #path::variant_name(self) {
+                    ::core::option::Option::Some(name) => f.write_str(name),
+                    ::core::option::Option::None => {
+                        ::core::fmt::Display::fmt(&#path::variant_index(self), f)
+                    }
+                }
+            }
+        }
+    }
+    .into()
+}
+
+/// 在各变体都没有字段的 enum 上实现 `FromStr`，使用 `algtype::variant_name::VariantName::from_variant_name`
+///
+/// 没有名字信息时解析变体的序号。
+#[proc_macro_derive(GenericFromStr)]
+pub fn derive_from_str(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let path = quote!(::algtype::variant_name::VariantName);
+    quote! {
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::algtype::variant_name::ParseVariantError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                let x = match <Self as ::algtype::Generic>::META {
+                    ::core::option::Option::Some(_) => <Self as #path>::from_variant_name(s),
+                    ::core::option::Option::None => s
+                        .parse()
+                        .ok()
+                        .and_then(<Self as #path>::from_variant_index),
+                };
+                x.ok_or_else(::algtype::variant_name::ParseVariantError::new::<Self>)
+            }
+        }
+    }
+    .into()
+}
+
+/// 逐字段的运算
+enum Op {
+    /// 如 `Add::add`，以 `ZipFields` 实现
+    Binary(&'static str, &'static str),
+    /// 如 `AddAssign::add_assign`，以 `ZipFieldsMut` 实现
+    Assign(&'static str, &'static str),
+    /// 如 `Neg::neg`，以 `MapFields` 实现
+    Unary(&'static str, &'static str),
+}
+
+/// 在仅有一个变体的类型上逐字段地实现 `ops` 中的运算
+fn pointwise(input: proc_macro::TokenStream, ops: &[Op]) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let preds = where_clause.into_iter().flat_map(|w| &w.predicates);
+    let path = quote!(::algtype::pointwise);
+    let impls = ops.iter().map(|op| {
+        let preds = preds.clone();
+        let (tr, method, body) = match *op {
+            Op::Binary(tr, method) => {
+                let f = format_ident!("{tr}Fn");
+                let (tr, method) = (format_ident!("{tr}"), format_ident!("{method}"));
+                let body = quote! {
+                    type Output = Self;
+
+                    #[inline]
+                    fn #method(self, rhs: Self) -> Self {
+                        #path::ZipFields::zip_fields(self, rhs, #path::#f)
+                    }
+                };
+                (quote!(#tr), quote!(#path::ZipFields<#path::#f>), body)
+            }
+            Op::Assign(tr, method) => {
+                let f = format_ident!("{tr}Fn");
+                let (tr, method) = (format_ident!("{tr}"), format_ident!("{method}"));
+                let body = quote! {
+                    #[inline]
+                    fn #method(&mut self, rhs: Self) {
+                        #path::ZipFieldsMut::zip_fields_mut(self, rhs, #path::#f)
+                    }
+                };
+                (quote!(#tr), quote!(#path::ZipFieldsMut<#path::#f>), body)
+            }
+            Op::Unary(tr, method) => {
+                let f = format_ident!("{tr}Fn");
+                let (tr, method) = (format_ident!("{tr}"), format_ident!("{method}"));
+                let body = quote! {
+                    type Output = Self;
+
+                    #[inline]
+                    fn #method(self) -> Self {
+                        #path::MapFields::map_fields(self, #path::#f)
+                    }
+                };
+                (quote!(#tr), quote!(#path::MapFields<#path::#f>), body)
+            }
+        };
+        quote! {
+            impl #impl_generics ::core::ops::#tr for #name #ty_generics
+            where
+                #(#preds,)*
+                Self: #method,
+            {
+                #body
+            }
+        }
+    });
+    quote!(#(#impls)*).into()
+}
+
+/// 在仅有一个变体的类型上逐字段地实现 `Add` 和 `AddAssign`
+#[proc_macro_derive(GenericAdd)]
+pub fn derive_add(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    pointwise(
+        input,
+        &[
+            Op::Binary("Add", "add"),
+            Op::Assign("AddAssign", "add_assign"),
+        ],
+    )
+}
+
+/// 在仅有一个变体的类型上逐字段地实现 `Sub` 和 `SubAssign`
+#[proc_macro_derive(GenericSub)]
+pub fn derive_sub(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    pointwise(
+        input,
+        &[
+            Op::Binary("Sub", "sub"),
+            Op::Assign("SubAssign", "sub_assign"),
+        ],
+    )
+}
+
+/// 在仅有一个变体的类型上逐字段地实现 `Neg`
+#[proc_macro_derive(GenericNeg)]
+pub fn derive_neg(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    pointwise(input, &[Op::Unary("Neg", "neg")])
+}
+
+/// 在仅有一个变体的类型上逐字段地实现 `Mul` 和 `MulAssign`
+#[proc_macro_derive(GenericMul)]
+pub fn derive_mul(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    pointwise(
+        input,
+        &[
+            Op::Binary("Mul", "mul"),
+            Op::Assign("MulAssign", "mul_assign"),
+        ],
+    )
+}
+
+/// 在 struct 或 enum 上实现 `count_enum::GenericEnum`
+///
+/// 默认要求每个字段的类型实现 `Enum`。
+/// 可用 `#[enum_bound(T: Enum)]` 之类的属性指定约束，以代替默认的。
+#[proc_macro_derive(GenericEnum, attributes(enum_bound))]
+pub fn derive_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    marker(
+        input,
+        quote!(::count_enum::GenericEnum),
+        quote!(::count_enum::Enum),
+        "enum_bound",
+    )
+}
+
+/// 在 struct 或 enum 上实现 `power_map::GenericMapKey`
+///
+/// 默认要求每个字段的类型实现 `MapKey`。
+/// 可用 `#[map_key_bound(T: MapKey)]` 之类的属性指定约束，以代替默认的。
+#[proc_macro_derive(GenericMapKey, attributes(map_key_bound))]
+pub fn derive_map_key(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    marker(
+        input,
+        quote!(::power_map::GenericMapKey),
+        quote!(::power_map::MapKey),
+        "map_key_bound",
+    )
+}
@@ -0,0 +1,101 @@
+//! `#[algtype(view = FooRef, mut_view = FooMut)]` 生成的引用形式
+
+use crate::flatten::is_flatten;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Fields, GenericParam, Lifetime, LifetimeParam};
+
+fn fields_mut(data: &mut Data) -> Vec<&mut Fields> {
+    match data {
+        Data::Struct(s) => vec![&mut s.fields],
+        Data::Enum(e) => e.variants.iter_mut().map(|v| &mut v.fields).collect(),
+        Data::Union(_) => vec![],
+    }
+}
+
+/// 生成名为 `name` 的引用形式，字段类型为 `&'a T` 或 `&'a mut T`
+pub fn view(input: &DeriveInput, name: &Ident, mutable: bool) -> syn::Result<DeriveInput> {
+    let mut view = input.clone();
+    if let Data::Union(_) = input.data {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "union is unsupported",
+        ));
+    }
+    if fields_mut(&mut view.data).iter().all(|f| f.is_empty()) {
+        return Err(syn::Error::new_spanned(
+            name,
+            "view of type without fields is unsupported",
+        ));
+    }
+    for f in fields_mut(&mut view.data)
+        .into_iter()
+        .flat_map(|f| f.iter())
+    {
+        if is_flatten(f)? {
+            return Err(syn::Error::new_spanned(
+                name,
+                "view cannot be used with flatten",
+            ));
+        }
+    }
+
+    // 选取不冲突的生存期名
+    let lt = (b'a'..=b'z')
+        .map(|c| Lifetime::new(&format!("'{}", c as char), Span::call_site()))
+        .find(|lt| view.generics.lifetimes().all(|l| l.lifetime != *lt))
+        .ok_or_else(|| syn::Error::new_spanned(name, "no lifetime name available"))?;
+    view.generics
+        .params
+        .insert(0, GenericParam::Lifetime(LifetimeParam::new(lt.clone())));
+
+    let orig = &input.ident;
+    let doc = format!(
+        " [`{orig}`] 的{}引用形式",
+        if mutable { "可变" } else { "" }
+    );
+    view.attrs = vec![parse_quote!(#[doc = #doc])];
+    view.ident = name.clone();
+    // 变体的属性（如 `#[default]`）和显式的判别值（需要已去掉的 `#[repr]`）不适用于引用形式
+    if let Data::Enum(e) = &mut view.data {
+        for v in e.variants.iter_mut() {
+            v.attrs.clear();
+            v.discriminant = None;
+        }
+    }
+    for fields in fields_mut(&mut view.data) {
+        for f in fields.iter_mut() {
+            let ty = &f.ty;
+            f.ty = match mutable {
+                true => parse_quote!(&#lt mut #ty),
+                false => parse_quote!(&#lt #ty),
+            };
+            f.attrs.clear();
+        }
+    }
+    Ok(view)
+}
+
+/// 定义类型
+pub fn define(view: &DeriveInput) -> TokenStream {
+    let DeriveInput {
+        vis,
+        ident,
+        generics,
+        attrs,
+        data,
+    } = view;
+    let (_, _, where_clause) = generics.split_for_impl();
+    let params = &generics.params;
+    match data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(f) => quote!(#(#attrs)* #vis struct #ident<#params> #where_clause #f),
+            f => quote!(#(#attrs)* #vis struct #ident<#params> #f #where_clause;),
+        },
+        Data::Enum(e) => {
+            let variants = &e.variants;
+            quote!(#(#attrs)* #vis enum #ident<#params> #where_clause { #variants })
+        }
+        Data::Union(_) => unreachable!(),
+    }
+}
@@ -2,15 +2,24 @@
 //!
 //! 它给值域一个顺序，序号和迭代之类都按照这个顺序。
 //!
-//! [`Enum`] 提供了基础的方法，可使用 [`GenericEnum`] 自动实现。
+//! [`Enum`] 提供了基础的方法，可使用 [`GenericEnum`] 自动实现，
+//! 同名的 derive 宏可以代替手写的 `impl GenericEnum for T {}`。
 //! 使用 [`iter_each`] 或 [`iter_each_from`] 迭代 [`Enum`] 的值域。
+//!
+//! 启用 `testing` 特性后，`testing` 模块提供检验 [`Enum`] 的实现的函数。
 
 #![no_std]
 
+#[cfg(feature = "testing")]
+extern crate std;
+
 mod generic;
 mod impls;
 mod iter;
+#[cfg(feature = "testing")]
+pub mod testing;
 
+pub use algtype_derive::GenericEnum;
 use core::num::NonZeroUsize;
 pub use generic::GenericEnum;
 pub use iter::{iter_each, iter_each_from, IterEachFrom};
@@ -0,0 +1,108 @@
+//! 检验 [`Enum`] 的实现
+//!
+//! 需启用 `testing` 特性。手写 [`Enum`] 的实现时，可用于测试。
+//!
+//! 检验的规律是：序号、上一个和下一个值、此后的值的总数、遍历的结果彼此一致。
+//! 值域可以为空，如 `enum Never {}`。
+
+use crate::Enum;
+use core::{fmt::Debug, num::NonZeroUsize};
+use proptest::{arbitrary::Arbitrary, proptest};
+
+fn assert_bounds<T: Enum + PartialEq + Debug>() {
+    let (Some(min), Some(max)) = (T::first(), T::last()) else {
+        assert_eq!(T::first(), None);
+        assert_eq!(T::last(), None);
+        assert_eq!(T::CARD, Some(0));
+        return;
+    };
+    assert_eq!(T::count_from(&min).map(NonZeroUsize::get), T::CARD);
+    assert_eq!(T::count_from(&max), Some(1.try_into().unwrap()));
+    assert_eq!(min.prev(), None);
+    assert_eq!(max.succ(), None);
+    assert_eq!(min.to_index(), Some(0));
+    assert_eq!(max.to_index().and_then(|x| x.checked_add(1)), T::CARD);
+}
+
+/// 检验从 `first` 开始的遍历与 `succ` 一致，即按顺序经过每个值
+fn assert_fold<T: Enum + PartialEq + Debug>() {
+    assert_eq!(
+        T::fold_each(T::first(), |x, y| {
+            assert_eq!(x, Some(y));
+            x.unwrap().succ()
+        }),
+        None
+    );
+}
+
+/// 对某个值检验序号、上一个和下一个值、此后的值的总数
+pub fn assert_enum_value<T: Enum + PartialEq + Debug>(x: &T) {
+    if let Some(i) = x.to_index() {
+        assert_eq!(Some(x), T::from_index(i).as_ref());
+        assert_eq!(i.checked_sub(1), x.prev().as_ref().and_then(T::to_index));
+    } else {
+        assert_eq!(None, x.succ().as_ref().and_then(T::to_index));
+    }
+
+    if let Some(y) = x.succ() {
+        assert_eq!(y.prev().as_ref(), Some(x));
+    }
+    if let Some(y) = x.prev() {
+        assert_eq!(y.succ().as_ref(), Some(x));
+    }
+
+    assert_eq!(
+        x.to_index()
+            .and_then(|i| T::count_from(x)?.get().checked_add(i)),
+        T::CARD
+    );
+}
+
+/// 对某个值检验从其开始的遍历与 `succ` 一致
+pub fn assert_enum_iter_from<T: Enum + PartialEq + Debug>(x: &T) {
+    assert_eq!(
+        T::fold_each_from(x, Some(x.clone()), |x, y| {
+            assert_eq!(x, Some(y));
+            x.unwrap().succ()
+        }),
+        None
+    );
+}
+
+/// 对首尾和随机的值检验 [`assert_enum_value`]
+///
+/// ```
+/// # use count_enum::testing::assert_enum;
+/// assert_enum::<Option<i128>>();
+/// ```
+pub fn assert_enum<T: Enum + PartialEq + Debug + Arbitrary>() {
+    assert_bounds::<T>();
+    proptest!(|(x: T)| assert_enum_value(&x));
+}
+
+/// 检验遍历与 `succ` 一致，并对随机的值检验 [`assert_enum_iter_from`]
+///
+/// 会遍历整个值域，仅适用于值域较小的类型。
+///
+/// ```
+/// # use count_enum::testing::assert_enum_iter;
+/// assert_enum_iter::<Option<(bool, u8)>>();
+/// ```
+pub fn assert_enum_iter<T: Enum + PartialEq + Debug + Arbitrary>() {
+    assert_fold::<T>();
+    proptest!(|(x: T)| assert_enum_iter_from(&x));
+}
+
+/// 对每个值检验 [`assert_enum_value`]，并检验遍历与 `succ` 一致
+///
+/// 会遍历整个值域，仅适用于值域较小的类型。
+///
+/// ```
+/// # use count_enum::testing::assert_enum_exhaustive;
+/// assert_enum_exhaustive::<Result<u8, Option<bool>>>();
+/// ```
+pub fn assert_enum_exhaustive<T: Enum + PartialEq + Debug>() {
+    assert_bounds::<T>();
+    assert_fold::<T>();
+    T::fold_each((), |(), x| assert_enum_value(&x));
+}
@@ -0,0 +1,43 @@
+use algtype::Generic;
+use count_enum::{testing::assert_enum_exhaustive, Enum, GenericEnum};
+
+#[derive(Clone, Generic, GenericEnum, Debug, PartialEq)]
+enum Inner {
+    A,
+    B(bool),
+}
+
+#[derive(Clone, Generic, GenericEnum, Debug, PartialEq)]
+enum Outer {
+    Inner(#[algtype(flatten)] Inner),
+    C(Option<bool>),
+}
+
+#[derive(Clone, Generic, GenericEnum, Debug, PartialEq)]
+enum Flat {
+    A,
+    B(bool),
+    C(Option<bool>),
+}
+
+#[test]
+fn test() {
+    assert_enum_exhaustive::<Outer>();
+    assert_eq!(Outer::CARD, Flat::CARD);
+    let outer = Outer::fold_each(Vec::new(), |mut v, x| {
+        v.push(x);
+        v
+    });
+    assert_eq!(outer.len(), 6);
+    for (i, o) in outer.into_iter().enumerate() {
+        assert_eq!(o.to_index(), Some(i));
+        assert_eq!(algtype::utils::cast::<_, Flat>(o).to_index(), Some(i));
+    }
+    let names: Vec<_> = Outer::META
+        .unwrap()
+        .variants
+        .iter()
+        .map(|v| v.name)
+        .collect();
+    assert_eq!(names, ["A", "B", "C"]);
+}
@@ -2,14 +2,23 @@
 //!
 //! [`TotalMap`] 提供了类似 `EnumMap` 的单射。
 //!
-//! 作为键者需实现 [`MapKey`]，可使用 [`GenericMapKey`] 自动实现。
+//! 作为键者需实现 [`MapKey`]，可使用 [`GenericMapKey`] 自动实现，
+//! 同名的 derive 宏可以代替手写的 `impl GenericMapKey for T {}`。
+//!
+//! 启用 `testing` 特性后，`testing` 模块提供检验 [`MapKey`] 的实现的函数。
 
 #![no_std]
 
+#[cfg(feature = "testing")]
+extern crate std;
+
 mod generic;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod totalmap;
 mod traits;
 
+pub use algtype_derive::GenericMapKey;
 pub use generic::GenericMapKey;
 pub use totalmap::TotalMap;
 pub use traits::MapKey;
@@ -0,0 +1,56 @@
+//! 检验 [`MapKey`] 的实现
+//!
+//! 需启用 `testing` 特性。手写 [`MapKey`] 的实现时，可用于测试。
+//!
+//! 检验的规律是：按键索引、`k_from_fn`、`k_map_with_key`、`k_into_values` 彼此一致。
+
+use crate::{MapKey, TotalMap};
+use core::fmt::Debug;
+use count_enum::Enum;
+use proptest::{arbitrary::Arbitrary, proptest};
+use std::vec::Vec;
+
+/// 依次修改各键的值，然后检验
+pub fn assert_map_keys<T: MapKey + Enum + PartialEq + Debug>(keys: &[T]) {
+    let mut m = TotalMap::from_fn(Ok);
+    for (i, k) in keys.iter().enumerate() {
+        m[k] = Err(i);
+    }
+
+    for (k, v) in m.as_ref().map_with_key(|k, _| k) {
+        assert_eq!(k, v);
+    }
+
+    for (k, v) in m {
+        match v {
+            Ok(v) => assert_eq!(k, v),
+            Err(v) => assert_eq!(k, keys[v]),
+        }
+    }
+}
+
+/// 对随机的键检验 [`assert_map_keys`]
+///
+/// ```
+/// # use power_map::testing::assert_map;
+/// assert_map::<Option<i8>>();
+/// ```
+pub fn assert_map<T: MapKey + Enum + PartialEq + Debug + Arbitrary>() {
+    proptest!(|(k: [T; 10])| assert_map_keys(&k));
+}
+
+/// 对所有的键检验 [`assert_map_keys`]
+///
+/// 会遍历整个值域，仅适用于值域较小的类型。
+///
+/// ```
+/// # use power_map::testing::assert_map_exhaustive;
+/// assert_map_exhaustive::<(bool, Option<u8>)>();
+/// ```
+pub fn assert_map_exhaustive<T: MapKey + Enum + PartialEq + Debug>() {
+    let keys = T::fold_each(Vec::new(), |mut v, k| {
+        v.push(k);
+        v
+    });
+    assert_map_keys(&keys);
+}